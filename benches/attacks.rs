@@ -0,0 +1,68 @@
+//! Slider lookups against the batched attack query. Run with
+//! `cargo bench --bench attacks`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use lemonate::{AttackTable, Bitboard, Board, Color, Piece, PieceType, Square};
+
+const ROUNDS: usize = 200_000;
+
+fn time(name: &str, lookups: usize, mut run: impl FnMut()) {
+    // One untimed pass so every case starts warm
+    run();
+    let start = Instant::now();
+    run();
+    let elapsed: Duration = start.elapsed();
+    println!(
+        "{name:<24} {:>10.2} ns/lookup",
+        elapsed.as_nanos() as f64 / (ROUNDS * lookups) as f64
+    );
+}
+
+fn main() {
+    let attacks = AttackTable::new();
+    // Kiwipete's occupancy, busy enough that the sliders are blocked
+    let occupancy =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap()
+            .occupancy();
+    let pieces: Vec<(Square, Piece)> = (0..64)
+        .step_by(3)
+        .map(|index| {
+            let queen = Piece {
+                piece_type: PieceType::Queen,
+                color: Color::White,
+            };
+            (Square::from_index(index), queen)
+        })
+        .collect();
+
+    time("queen_attacks", pieces.len(), || {
+        for _ in 0..ROUNDS {
+            let mut union = Bitboard::EMPTY;
+            for &(square, _) in &pieces {
+                union |= attacks.queen_attacks(black_box(square), occupancy);
+            }
+            black_box(union);
+        }
+    });
+
+    time("rook + bishop", pieces.len(), || {
+        for _ in 0..ROUNDS {
+            let mut union = Bitboard::EMPTY;
+            for &(square, _) in &pieces {
+                let square = black_box(square);
+                union |= attacks.rook_attacks(square, occupancy);
+                union |= attacks.bishop_attacks(square, occupancy);
+            }
+            black_box(union);
+        }
+    });
+
+    time("multi_attacks", pieces.len(), || {
+        for _ in 0..ROUNDS {
+            black_box(attacks.multi_attacks(black_box(&pieces), occupancy));
+        }
+    });
+}
@@ -0,0 +1,29 @@
+//! Magic number search from a seed, sequential or parallel depending on
+//! the `parallel` feature. Compare `cargo bench --bench magic` against
+//! `cargo bench --bench magic --features parallel`.
+
+use std::time::Instant;
+
+use lemonate::{
+    AttackTable, DEFAULT_MAGIC_SEED, init_bishop_magics_with_seed, init_rook_magics_with_seed,
+};
+
+fn main() {
+    let mode = if cfg!(feature = "parallel") {
+        "parallel"
+    } else {
+        "sequential"
+    };
+
+    let start = Instant::now();
+    init_rook_magics_with_seed(DEFAULT_MAGIC_SEED).unwrap();
+    println!("rook search ({mode})     {:>10.2?}", start.elapsed());
+
+    let start = Instant::now();
+    init_bishop_magics_with_seed(DEFAULT_MAGIC_SEED).unwrap();
+    println!("bishop search ({mode})   {:>10.2?}", start.elapsed());
+
+    let start = Instant::now();
+    AttackTable::new();
+    println!("AttackTable::new (embedded) {:>10.2?}", start.elapsed());
+}
@@ -0,0 +1,52 @@
+//! Vec against MoveList generation over a few perft positions. Run with
+//! `cargo bench --bench movegen`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use lemonate::{AttackTable, Board, MoveList};
+
+const POSITIONS: [&str; 3] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+];
+const ROUNDS: usize = 20_000;
+
+fn time(name: &str, mut run: impl FnMut()) {
+    // One untimed pass so both sides start warm
+    run();
+    let start = Instant::now();
+    run();
+    let elapsed: Duration = start.elapsed();
+    println!(
+        "{name:<24} {:>10.1} ns/position",
+        elapsed.as_nanos() as f64 / (ROUNDS * POSITIONS.len()) as f64
+    );
+}
+
+fn main() {
+    let attacks = AttackTable::new();
+    let boards: Vec<Board> = POSITIONS
+        .iter()
+        .map(|fen| Board::from_fen(fen).unwrap())
+        .collect();
+
+    time("generate_legal (Vec)", || {
+        for _ in 0..ROUNDS {
+            for board in &boards {
+                black_box(board.generate_legal(&attacks));
+            }
+        }
+    });
+
+    let mut list = MoveList::new();
+    time("generate_legal_into", || {
+        for _ in 0..ROUNDS {
+            for board in &boards {
+                board.generate_legal_into(&mut list, &attacks);
+                black_box(&list);
+            }
+        }
+    });
+}
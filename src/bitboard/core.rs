@@ -1,12 +1,66 @@
-use crate::types::Square;
+use crate::magic::{calculate_bishop_attacks, calculate_rook_attacks};
+use crate::types::{Color, Direction, Square};
+use alloc::vec::Vec;
+
+const NOT_A_FILE: u64 = !Bitboard::FILES[0].0;
+const NOT_H_FILE: u64 = !Bitboard::FILES[7].0;
+const NOT_AB_FILES: u64 = NOT_A_FILE & !Bitboard::FILES[1].0;
+const NOT_GH_FILES: u64 = NOT_H_FILE & !Bitboard::FILES[6].0;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bitboard(pub u64);
 
 impl Bitboard {
     pub const EMPTY: Self = Self(0);
     pub const FULL: Self = Self(u64::MAX);
 
+    /// File masks, a-file first.
+    pub const FILES: [Self; 8] = {
+        let mut files = [Self::EMPTY; 8];
+        let mut file = 0;
+        while file < 8 {
+            files[file] = Self(0x0101_0101_0101_0101 << file);
+            file += 1;
+        }
+        files
+    };
+
+    /// Rank masks, rank 1 first.
+    pub const RANKS: [Self; 8] = {
+        let mut ranks = [Self::EMPTY; 8];
+        let mut rank = 0;
+        while rank < 8 {
+            ranks[rank] = Self(0xff << (rank * 8));
+            rank += 1;
+        }
+        ranks
+    };
+
+    /// Every square on file `file` (0 = a). Wraps like `Square::from_coords`.
+    pub const fn file(file: u8) -> Self {
+        Self::FILES[(file & 7) as usize]
+    }
+
+    /// Every square on rank `rank` (0 = rank 1).
+    pub const fn rank(rank: u8) -> Self {
+        Self::RANKS[(rank & 7) as usize]
+    }
+
+    pub const fn from_square(square: Square) -> Self {
+        Self(1u64 << square.index())
+    }
+
+    pub const fn from_squares(squares: &[Square]) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < squares.len() {
+            bits |= 1u64 << squares[i].index();
+            i += 1;
+        }
+        Self(bits)
+    }
+
     // Set ops
     pub fn is_set(self, square: Square) -> bool {
         let bitmask = 1u64 << square.index();
@@ -50,6 +104,66 @@ impl Bitboard {
             Some(Square::from_index(square_index))
         }
     }
+    /// The set squares in ascending index order, the inverse of `from_squares`.
+    pub fn squares(&self) -> Vec<Square> {
+        self.iter().collect()
+    }
+
+    /// Iterates the set squares from a1 towards h8 without touching `self`.
+    pub fn iter(&self) -> BitIter {
+        BitIter { remaining: self.0 }
+    }
+
+    /// Every subset of the set bits, the empty set first and `self` last,
+    /// in the order `generate_blocker_board` numbers them.
+    pub fn subsets(self) -> SubsetIter {
+        SubsetIter {
+            mask: self.0,
+            next: Some(0),
+        }
+    }
+
+    /// Gathers the bits of `self` under `mask` into the low bits of the
+    /// result, lowest mask bit first. The BMI2 instruction when the build
+    /// targets it, a loop over the mask otherwise.
+    pub fn pext(self, mask: Bitboard) -> u64 {
+        #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+        {
+            // SAFETY: the build only targets CPUs with BMI2
+            unsafe { core::arch::x86_64::_pext_u64(self.0, mask.0) }
+        }
+        #[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+        {
+            let mut result = 0;
+            for (bit, square) in mask.iter().enumerate() {
+                if self.is_set(square) {
+                    result |= 1 << bit;
+                }
+            }
+            result
+        }
+    }
+
+    /// The inverse of `pext`, spreading the low bits of `bits` out over
+    /// the squares of `mask` in ascending order.
+    pub fn pdep(bits: u64, mask: Bitboard) -> Bitboard {
+        #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+        {
+            // SAFETY: the build only targets CPUs with BMI2
+            Bitboard(unsafe { core::arch::x86_64::_pdep_u64(bits, mask.0) })
+        }
+        #[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+        {
+            let mut result = Bitboard::EMPTY;
+            for (bit, square) in mask.iter().enumerate() {
+                if bits & (1 << bit) != 0 {
+                    result.set(square);
+                }
+            }
+            result
+        }
+    }
+
     /// Returns the number of leading zeros.
     /// Returns 64 if the bitboard is empty.
     pub fn leading_zeros(&self) -> u32 {
@@ -60,56 +174,229 @@ impl Bitboard {
         self.0.trailing_zeros()
     }
 
+    /// Moves every set bit one step in `dir`. Bits that would wrap around
+    /// to the opposite file, or fall off the top/bottom, are dropped.
+    pub const fn shift(self, dir: Direction) -> Self {
+        let value = dir.value();
+        let shifted = if value > 0 {
+            self.0 << value
+        } else {
+            self.0 >> -value
+        };
+
+        match dir.deltas().0 {
+            1 => Self(shifted & NOT_A_FILE),
+            -1 => Self(shifted & NOT_H_FILE),
+            _ => Self(shifted),
+        }
+    }
+
+    /// `shift` applied `steps` times, so every bit that would cross an
+    /// edge on the way is dropped rather than wrapped.
+    pub const fn shift_by(self, dir: Direction, steps: u32) -> Self {
+        let mut shifted = self;
+        let mut step = 0;
+        while step < steps && shifted.0 != 0 {
+            shifted = shifted.shift(dir);
+            step += 1;
+        }
+        shifted
+    }
+
+    /// Every square some knight in `knights` attacks, for the whole set at
+    /// once and without the attack table.
+    pub const fn knight_attacks_all(knights: Bitboard) -> Self {
+        let b = knights.0;
+        // One and two files over, with the bits that wrapped dropped
+        let one = ((b << 1) & NOT_A_FILE) | ((b >> 1) & NOT_H_FILE);
+        let two = ((b << 2) & NOT_AB_FILES) | ((b >> 2) & NOT_GH_FILES);
+        Self((one << 16) | (one >> 16) | (two << 8) | (two >> 8))
+    }
+
+    /// Where each of `color`'s `pawns` lands with a single push onto an
+    /// `empty` square. Promotions included, they are just pushes onto the
+    /// last rank.
+    pub const fn pawn_single_pushes(pawns: Bitboard, empty: Bitboard, color: Color) -> Self {
+        Self(pawns.shift(Direction::NORTH.relative(color)).0 & empty.0)
+    }
+
+    /// Where each of `color`'s `pawns` lands with a double push. Both the
+    /// square in front and the destination must be `empty`, so only pawns
+    /// still on their starting rank can reach the fourth rank this way.
+    pub const fn pawn_double_pushes(pawns: Bitboard, empty: Bitboard, color: Color) -> Self {
+        let fourth_rank = match color {
+            Color::White => Self::RANKS[3],
+            Color::Black => Self::RANKS[4],
+        };
+        let single = Self::pawn_single_pushes(pawns, empty, color);
+        Self(Self::pawn_single_pushes(single, empty, color).0 & fourth_rank.0)
+    }
+
+    /// Mirrors ranks, a1 <-> a8.
+    pub const fn flip_vertical(self) -> Self {
+        Self(self.0.swap_bytes())
+    }
+
+    /// Mirrors files, a1 <-> h1.
+    pub const fn flip_horizontal(self) -> Self {
+        const K1: u64 = 0x5555_5555_5555_5555;
+        const K2: u64 = 0x3333_3333_3333_3333;
+        const K4: u64 = 0x0f0f_0f0f_0f0f_0f0f;
+
+        let mut x = self.0;
+        x = ((x >> 1) & K1) | ((x & K1) << 1);
+        x = ((x >> 2) & K2) | ((x & K2) << 2);
+        x = ((x >> 4) & K4) | ((x & K4) << 4);
+        Self(x)
+    }
+
+    /// Mirrors along the a1-h8 diagonal, a8 <-> h1.
+    pub const fn flip_diagonal(self) -> Self {
+        const K1: u64 = 0x5500_5500_5500_5500;
+        const K2: u64 = 0x3333_0000_3333_0000;
+        const K4: u64 = 0x0f0f_0f0f_0000_0000;
+
+        let mut x = self.0;
+        let mut t = K4 & (x ^ (x << 28));
+        x ^= t ^ (t >> 28);
+        t = K2 & (x ^ (x << 14));
+        x ^= t ^ (t >> 14);
+        t = K1 & (x ^ (x << 7));
+        x ^= t ^ (t >> 7);
+        Self(x)
+    }
+
+    /// Smears every set bit up its file towards rank 8.
+    pub const fn north_fill(self) -> Self {
+        let mut x = self.0;
+        x |= x << 8;
+        x |= x << 16;
+        x |= x << 32;
+        Self(x)
+    }
+
+    /// Smears every set bit down its file towards rank 1.
+    pub const fn south_fill(self) -> Self {
+        let mut x = self.0;
+        x |= x >> 8;
+        x |= x >> 16;
+        x |= x >> 32;
+        Self(x)
+    }
+
+    /// Every file that has at least one bit set, filled completely.
+    pub const fn file_fill(self) -> Self {
+        Self(self.north_fill().0 | self.south_fill().0)
+    }
+
+    /// The squares a slider on `from` reaches going in `dir`, up to and
+    /// including the first one in `occupancy`.
+    pub const fn ray_attacks(from: Square, dir: Direction, occupancy: Bitboard) -> Self {
+        let mut ray = 0;
+        let mut current = Self::from_square(from);
+        loop {
+            current = current.shift(dir);
+            ray |= current.0;
+            if current.0 == 0 || current.0 & occupancy.0 != 0 {
+                return Self(ray);
+            }
+        }
+    }
+
+    /// Squares strictly between `a` and `b` when they share a rank, file
+    /// or diagonal, otherwise empty.
+    pub fn between(a: Square, b: Square) -> Self {
+        let a_bb = Self::from_square(a);
+        let b_bb = Self::from_square(b);
+
+        let rook = calculate_rook_attacks(a, b_bb);
+        if rook.is_set(b) {
+            return rook & calculate_rook_attacks(b, a_bb);
+        }
+
+        let bishop = calculate_bishop_attacks(a, b_bb);
+        if bishop.is_set(b) {
+            return bishop & calculate_bishop_attacks(b, a_bb);
+        }
+
+        Self::EMPTY
+    }
+
+    /// The lowest set square, the same one `pop_lsb` would return.
     pub fn first_square(&self) -> Option<Square> {
         if self.0 == 0 {
             None
         } else {
-            Some(Square::from_index(self.0.leading_zeros() as usize))
+            Some(Square::from_index(self.0.trailing_zeros() as usize))
         }
     }
+
+    /// The highest set square.
+    pub fn last_square(&self) -> Option<Square> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(Square::from_index(63 - self.0.leading_zeros() as usize))
+        }
+    }
+
+    /// Index of the lowest set bit, for callers that already know the
+    /// board is not empty. On an empty board this trips a debug assertion
+    /// and otherwise returns 64, which is no square.
+    pub const fn bsf(self) -> u32 {
+        debug_assert!(self.0 != 0, "bit scan of an empty bitboard");
+        self.0.trailing_zeros()
+    }
+
+    /// Index of the highest set bit, see `bsf`. On an empty board the
+    /// result wraps to a meaningless value in release builds.
+    pub const fn bsr(self) -> u32 {
+        debug_assert!(self.0 != 0, "bit scan of an empty bitboard");
+        63u32.wrapping_sub(self.0.leading_zeros())
+    }
 }
 
-impl std::ops::BitOr for Bitboard {
+impl core::ops::BitOr for Bitboard {
     type Output = Self;
     fn bitor(self, rhs: Self) -> Self {
         Self(self.0 | rhs.0)
     }
 }
 
-impl std::ops::BitAnd for Bitboard {
+impl core::ops::BitAnd for Bitboard {
     type Output = Self;
     fn bitand(self, rhs: Self) -> Self {
         Self(self.0 & rhs.0)
     }
 }
 
-impl std::ops::BitXor for Bitboard {
+impl core::ops::BitXor for Bitboard {
     type Output = Self;
     fn bitxor(self, rhs: Self) -> Self {
         Self(self.0 ^ rhs.0)
     }
 }
 
-impl std::ops::Not for Bitboard {
+impl core::ops::Not for Bitboard {
     type Output = Self;
     fn not(self) -> Self {
         Self(!self.0)
     }
 }
 
-impl std::ops::BitOrAssign for Bitboard {
+impl core::ops::BitOrAssign for Bitboard {
     fn bitor_assign(&mut self, rhs: Self) {
         self.0 |= rhs.0
     }
 }
 
-impl std::ops::BitAndAssign for Bitboard {
+impl core::ops::BitAndAssign for Bitboard {
     fn bitand_assign(&mut self, rhs: Self) {
         self.0 &= rhs.0
     }
 }
 
-impl std::ops::BitXorAssign for Bitboard {
+impl core::ops::BitXorAssign for Bitboard {
     fn bitxor_assign(&mut self, rhs: Self) {
         self.0 ^= rhs.0
     }
@@ -123,22 +410,69 @@ impl Iterator for Bitboard {
     }
 }
 
-impl std::ops::Shl<u32> for Bitboard {
+pub struct BitIter {
+    remaining: u64,
+}
+
+impl Iterator for BitIter {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let square_index = self.remaining.trailing_zeros() as usize;
+        self.remaining &= self.remaining - 1;
+        Some(Square::from_index(square_index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.remaining.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for BitIter {}
+
+pub struct SubsetIter {
+    mask: u64,
+    // None once the walk has wrapped back round to the empty set
+    next: Option<u64>,
+}
+
+impl Iterator for SubsetIter {
+    type Item = Bitboard;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let subset = self.next?;
+        // Carry-Rippler: count upwards through the mask bits only
+        let following = subset.wrapping_sub(self.mask) & self.mask;
+        self.next = if following == 0 {
+            None
+        } else {
+            Some(following)
+        };
+        Some(Bitboard(subset))
+    }
+}
+
+impl core::ops::Shl<u32> for Bitboard {
     type Output = Self;
     fn shl(self, rhs: u32) -> Self::Output {
         Self(self.0 << rhs)
     }
 }
 
-impl std::ops::Shr<u32> for Bitboard {
+impl core::ops::Shr<u32> for Bitboard {
     type Output = Self;
     fn shr(self, rhs: u32) -> Self::Output {
         Self(self.0 >> rhs)
     }
 }
 
-impl std::fmt::Display for Bitboard {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Bitboard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for rank in (0..8).rev() {
             for file in 0..8 {
                 let square_index = rank * 8 + file;
@@ -0,0 +1,188 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::board::castling::castling_side;
+use crate::magic::AttackTable;
+use crate::types::{Color, Direction, Move, MoveFlag, PieceType, Square};
+
+impl Board {
+    /// Whether any piece of color `by` attacks `square`, using the current
+    /// occupancy as blockers for sliders. En passant is not considered,
+    /// it can only ever capture a pawn, never attack a square.
+    pub fn is_square_attacked(&self, square: Square, by: Color, attacks: &AttackTable) -> bool {
+        let pieces = &self.piece_bitboards[by.index()];
+        let defender = by.opposite();
+
+        let diagonal = pieces[PieceType::Bishop.index()] | pieces[PieceType::Queen.index()];
+        let orthogonal = pieces[PieceType::Rook.index()] | pieces[PieceType::Queen.index()];
+
+        // A pawn of `by` attacks `square` from wherever a defending pawn
+        // on `square` would attack
+        (attacks.pawn_attacks(square, defender) & pieces[PieceType::Pawn.index()]).is_not_empty()
+            || (attacks.knight_attacks(square) & pieces[PieceType::Knight.index()]).is_not_empty()
+            || (attacks.king_attacks(square) & pieces[PieceType::King.index()]).is_not_empty()
+            || (attacks.bishop_attacks(square, self.all_pieces) & diagonal).is_not_empty()
+            || (attacks.rook_attacks(square, self.all_pieces) & orthogonal).is_not_empty()
+    }
+
+    /// Every square a piece of `color` attacks, own pieces' squares
+    /// included. The enemy king does not block sliders, so the squares
+    /// behind it along a checking line count as attacked too, which is
+    /// where the king cannot step to get out of check.
+    pub fn attacks_by(&self, color: Color, attacks: &AttackTable) -> Bitboard {
+        let pieces = &self.piece_bitboards[color.index()];
+        let enemy_king = self.piece_bitboards[color.opposite().index()][PieceType::King.index()];
+        let occupancy = self.all_pieces & !enemy_king;
+
+        let pawns = pieces[PieceType::Pawn.index()];
+        let mut attacked = match color {
+            Color::White => pawns.shift(Direction::NORTHEAST) | pawns.shift(Direction::NORTHWEST),
+            Color::Black => pawns.shift(Direction::SOUTHEAST) | pawns.shift(Direction::SOUTHWEST),
+        };
+        attacked |= Bitboard::knight_attacks_all(pieces[PieceType::Knight.index()]);
+
+        for square in pieces[PieceType::Bishop.index()] | pieces[PieceType::Queen.index()] {
+            attacked |= attacks.bishop_attacks(square, occupancy);
+        }
+        for square in pieces[PieceType::Rook.index()] | pieces[PieceType::Queen.index()] {
+            attacked |= attacks.rook_attacks(square, occupancy);
+        }
+        for square in pieces[PieceType::King.index()] {
+            attacked |= attacks.king_attacks(square);
+        }
+
+        attacked
+    }
+
+    /// Whether the side to move's king is attacked.
+    pub fn in_check(&self, attacks: &AttackTable) -> bool {
+        self.checkers(attacks).is_not_empty()
+    }
+
+    /// Enemy pieces giving check to the side to move. Two bits set means
+    /// double check, where only a king move can be legal.
+    pub fn checkers(&self, attacks: &AttackTable) -> Bitboard {
+        let us = self.side_to_move;
+        let them = us.opposite();
+
+        let mut kings = self.piece_bitboards[us.index()][PieceType::King.index()];
+        let Some(king) = kings.pop_lsb() else {
+            return Bitboard::EMPTY;
+        };
+
+        let pieces = &self.piece_bitboards[them.index()];
+        let diagonal = pieces[PieceType::Bishop.index()] | pieces[PieceType::Queen.index()];
+        let orthogonal = pieces[PieceType::Rook.index()] | pieces[PieceType::Queen.index()];
+
+        (attacks.pawn_attacks(king, us) & pieces[PieceType::Pawn.index()])
+            | (attacks.knight_attacks(king) & pieces[PieceType::Knight.index()])
+            | (attacks.bishop_attacks(king, self.all_pieces) & diagonal)
+            | (attacks.rook_attacks(king, self.all_pieces) & orthogonal)
+    }
+
+    /// Whether playing `mv`, assumed legal, leaves the opponent in check.
+    /// Worked out from where the pieces end up rather than by making the
+    /// move, so it sees direct checks, sliders uncovered by the moving
+    /// piece or an en passant capture, the rook of a castling move and the
+    /// piece a pawn promotes to.
+    pub fn gives_check(&self, mv: Move, attacks: &AttackTable) -> bool {
+        let us = self.side_to_move;
+        let them = us.opposite();
+        let Some(king) = self.piece_bitboards[them.index()][PieceType::King.index()].first_square()
+        else {
+            return false;
+        };
+
+        let from = mv.from();
+        let to = mv.to();
+        let Some(moved) = self.piece_at(from) else {
+            return false;
+        };
+        let placed = mv.promotion_piece().unwrap_or(moved.piece_type);
+
+        let mut occupancy = self.all_pieces & !Bitboard::from_square(from);
+        if mv.flag() == MoveFlag::EnPassant {
+            occupancy.clear(Square::from_coords(to.file(), from.rank()));
+        }
+
+        // Castling moves the rook as well, onto the square the king crossed
+        let mut rook_move = None;
+        if mv.is_castle() {
+            let squares = self.castling_squares(us, castling_side(mv));
+            occupancy.clear(squares.rook_from);
+            occupancy.set(squares.rook_to);
+            rook_move = Some((squares.rook_from, squares.rook_to));
+        }
+        occupancy.set(to);
+
+        let ours = |piece_type: PieceType| {
+            let mut pieces = self.piece_bitboards[us.index()][piece_type.index()];
+            pieces.clear(from);
+            if piece_type == placed {
+                pieces.set(to);
+            }
+            if piece_type == PieceType::Rook
+                && let Some((rook_from, rook_to)) = rook_move
+            {
+                pieces.clear(rook_from);
+                pieces.set(rook_to);
+            }
+            pieces
+        };
+
+        let diagonal = ours(PieceType::Bishop) | ours(PieceType::Queen);
+        let orthogonal = ours(PieceType::Rook) | ours(PieceType::Queen);
+
+        (attacks.pawn_attacks(king, them) & ours(PieceType::Pawn)).is_not_empty()
+            || (attacks.knight_attacks(king) & ours(PieceType::Knight)).is_not_empty()
+            || (attacks.bishop_attacks(king, occupancy) & diagonal).is_not_empty()
+            || (attacks.rook_attacks(king, occupancy) & orthogonal).is_not_empty()
+    }
+
+    /// Side to move pieces that are absolutely pinned to their own king.
+    ///
+    /// This does not cover the en passant case where capturing removes
+    /// two pawns from a rank and exposes the king sideways, legal move
+    /// filtering catches that one when it makes the move.
+    pub fn pinned_pieces(&self, attacks: &AttackTable) -> Bitboard {
+        let us = self.side_to_move;
+        let them = us.opposite();
+
+        let mut kings = self.piece_bitboards[us.index()][PieceType::King.index()];
+        let Some(king) = kings.pop_lsb() else {
+            return Bitboard::EMPTY;
+        };
+
+        let pieces = &self.piece_bitboards[them.index()];
+        let enemies = self.color_bitboard[them.index()];
+        let diagonal = pieces[PieceType::Bishop.index()] | pieces[PieceType::Queen.index()];
+        let orthogonal = pieces[PieceType::Rook.index()] | pieces[PieceType::Queen.index()];
+
+        // Sliders that would hit the king if only enemy pieces were on the board
+        let snipers = (attacks.bishop_attacks(king, enemies) & diagonal)
+            | (attacks.rook_attacks(king, enemies) & orthogonal);
+
+        let mut pinned = Bitboard::EMPTY;
+        for sniper in snipers {
+            let blockers = attacks.ray_between(king, sniper) & self.all_pieces;
+            if blockers.count_pieces() == 1 {
+                pinned |= blockers & self.color_bitboard[us.index()];
+            }
+        }
+
+        pinned
+    }
+
+    /// The line a piece on `square` may move along. For a pinned piece this
+    /// is the line through its king and the pinner, otherwise every square.
+    pub fn pin_ray(&self, square: Square, attacks: &AttackTable) -> Bitboard {
+        if !self.pinned_pieces(attacks).is_set(square) {
+            return Bitboard::FULL;
+        }
+
+        let mut kings = self.piece_bitboards[self.side_to_move.index()][PieceType::King.index()];
+        match kings.pop_lsb() {
+            Some(king) => attacks.line_through(king, square),
+            None => Bitboard::FULL,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::board::{Board, PositionError};
+use crate::magic::AttackTable;
+use crate::types::castling::CastlingRights;
+use crate::types::{Color, Piece, Square};
+
+/// Sets up an arbitrary position piece by piece, for when writing out a
+/// FEN by hand is more error prone than naming the squares.
+///
+/// Starts from an empty board with white to move, no castling rights, no
+/// en passant square and fresh move counters. `build` checks the result
+/// with [`Board::validate`], so a forgotten king or an impossible castling
+/// right is reported rather than turning up later.
+#[derive(Clone, Debug)]
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    pub fn new() -> Self {
+        let mut board = Board::new();
+        board.castling_rights = CastlingRights::none();
+        board.position_hash = board.compute_hash();
+        Self { board }
+    }
+
+    /// Puts `piece` on `square`, replacing whatever was there.
+    pub fn piece(mut self, square: Square, piece: Piece) -> Self {
+        self.board.remove_piece(square);
+        self.board.place_piece(square, piece);
+        self
+    }
+
+    pub fn side_to_move(mut self, color: Color) -> Self {
+        self.board.side_to_move = color;
+        self
+    }
+
+    pub fn castling(mut self, rights: CastlingRights) -> Self {
+        self.board.castling_rights = rights;
+        self
+    }
+
+    pub fn en_passant(mut self, square: Square) -> Self {
+        self.board.en_passant_square = Some(square);
+        self
+    }
+
+    pub fn halfmove(mut self, clock: u16) -> Self {
+        self.board.halfmove_clock = clock;
+        self
+    }
+
+    pub fn fullmove(mut self, number: u16) -> Self {
+        self.board.fullmove_number = number;
+        self
+    }
+
+    /// The position set up so far, if it passes [`Board::validate`].
+    pub fn build(self, attacks: &AttackTable) -> Result<Board, PositionError> {
+        let mut board = self.board;
+        board.position_hash = board.compute_hash();
+        board.validate(attacks)?;
+        Ok(board)
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
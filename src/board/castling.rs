@@ -0,0 +1,327 @@
+use crate::FenError;
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::types::{CastlingRights, CastlingSide, Color, Move, MoveFlag, PieceType, Square};
+use alloc::string::String;
+
+/// Which castling rules a board plays by. Either way the king ends up on
+/// the c or g file and the rook beside it on the d or f file; Chess960
+/// only lets them start from other files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Fischer random chess. The king starts anywhere between its two
+    /// rooks on the back rank, and castling moves are written as the king
+    /// taking its own rook in UCI.
+    Chess960,
+}
+
+// The files the king and each castling rook start on, by color. Castling
+// rights only ever refer to pieces still standing on these squares
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct CastlingFiles {
+    king: [u8; 2],
+    // Kingside rook first
+    rooks: [[u8; 2]; 2],
+}
+
+impl CastlingFiles {
+    pub(crate) const STANDARD: Self = Self {
+        king: [4, 4],
+        rooks: [[7, 0], [7, 0]],
+    };
+
+    pub(crate) const fn flip(self) -> Self {
+        Self {
+            king: [self.king[1], self.king[0]],
+            rooks: [self.rooks[1], self.rooks[0]],
+        }
+    }
+}
+
+/// Where the king and rook start and land for one castling move.
+pub(crate) struct CastlingSquares {
+    pub king_from: Square,
+    pub king_to: Square,
+    pub rook_from: Square,
+    pub rook_to: Square,
+}
+
+impl CastlingSquares {
+    // Everything the king and rook cross or land on, apart from the two
+    // of them. It must be empty to castle
+    fn path(&self) -> Bitboard {
+        (rank_span(self.king_from, self.king_to) | rank_span(self.rook_from, self.rook_to))
+            & !Bitboard::from_square(self.king_from)
+            & !Bitboard::from_square(self.rook_from)
+    }
+}
+
+/// The side a castling move castles to.
+pub(crate) const fn castling_side(mv: Move) -> CastlingSide {
+    match mv.flag() {
+        MoveFlag::QueenCastle => CastlingSide::Queenside,
+        _ => CastlingSide::Kingside,
+    }
+}
+
+/// Every square of one rank from `a` to `b`, both included.
+pub(crate) fn rank_span(a: Square, b: Square) -> Bitboard {
+    let (low, high) = if a.index() <= b.index() {
+        (a.index(), b.index())
+    } else {
+        (b.index(), a.index())
+    };
+    Bitboard((u64::MAX >> (63 - high)) & (u64::MAX << low))
+}
+
+const fn back_rank(color: Color) -> u8 {
+    match color {
+        Color::White => 0,
+        Color::Black => 7,
+    }
+}
+
+const fn side_index(side: CastlingSide) -> usize {
+    match side {
+        CastlingSide::Kingside => 0,
+        CastlingSide::Queenside => 1,
+    }
+}
+
+impl Board {
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Changes how castling is written, in the FEN castling field and in
+    /// UCI moves, without touching the position. `from_fen` already picks
+    /// Chess960 for any position standard chess could not castle in.
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
+
+    pub(crate) fn castling_squares(&self, color: Color, side: CastlingSide) -> CastlingSquares {
+        let rank = back_rank(color);
+        let (king_to, rook_to) = match side {
+            CastlingSide::Kingside => (6, 5),
+            CastlingSide::Queenside => (2, 3),
+        };
+        let files = &self.castling_files;
+
+        CastlingSquares {
+            king_from: Square::from_coords(files.king[color.index()], rank),
+            king_to: Square::from_coords(king_to, rank),
+            rook_from: Square::from_coords(files.rooks[color.index()][side_index(side)], rank),
+            rook_to: Square::from_coords(rook_to, rank),
+        }
+    }
+
+    // Only checks rights, that the king and rook are home and that the
+    // path is clear. Attacked squares are left to legality filtering
+    pub(crate) fn castling_move(&self, color: Color, side: CastlingSide) -> Option<Move> {
+        if !self.castling_rights.can_castle(color, side) {
+            return None;
+        }
+
+        let squares = self.castling_squares(color, side);
+        let pieces = &self.piece_bitboards[color.index()];
+        if !pieces[PieceType::King.index()].is_set(squares.king_from)
+            || !pieces[PieceType::Rook.index()].is_set(squares.rook_from)
+            || (squares.path() & self.all_pieces).is_not_empty()
+        {
+            return None;
+        }
+
+        Some(match side {
+            CastlingSide::Kingside => Move::king_castle(squares.king_from, squares.king_to),
+            CastlingSide::Queenside => Move::queen_castle(squares.king_from, squares.king_to),
+        })
+    }
+
+    // Lifts the king and rook before putting either down, in Chess960 one
+    // may land where the other stood. `undo` puts them back
+    pub(crate) fn move_castling_pieces(&mut self, color: Color, side: CastlingSide, undo: bool) {
+        let squares = self.castling_squares(color, side);
+        let (king_from, king_to, rook_from, rook_to) = match undo {
+            false => (
+                squares.king_from,
+                squares.king_to,
+                squares.rook_from,
+                squares.rook_to,
+            ),
+            true => (
+                squares.king_to,
+                squares.king_from,
+                squares.rook_to,
+                squares.rook_from,
+            ),
+        };
+
+        let king = self
+            .remove_piece(king_from)
+            .expect("castling without a king in place");
+        let rook = self
+            .remove_piece(rook_from)
+            .expect("castling without a rook in place");
+        self.place_piece(king_to, king);
+        self.place_piece(rook_to, rook);
+    }
+
+    // Any move from or to the home square of a king or castling rook means
+    // it has moved or been captured, so the matching rights are gone for good
+    pub(crate) fn update_castling_rights(&mut self, square: Square) {
+        for color in Color::ALL {
+            if square.rank() != back_rank(color) {
+                continue;
+            }
+
+            let files = &self.castling_files;
+            let file = square.file();
+            if file == files.king[color.index()] {
+                self.castling_rights.remove_color(color);
+            } else if file == files.rooks[color.index()][0] {
+                self.castling_rights.remove_kingside(color);
+            } else if file == files.rooks[color.index()][1] {
+                self.castling_rights.remove_queenside(color);
+            }
+        }
+    }
+
+    /// Reads the FEN castling field, in plain, X-FEN or Shredder-FEN form.
+    /// The pieces must already be placed.
+    ///
+    /// A file letter names the rook's file outright. `K` and `Q` mean the
+    /// outermost rook on that side of the king, as X-FEN has it, or the
+    /// corner if there is none. For an ordinary FEN that is the corner rook.
+    pub(crate) fn parse_castling(&mut self, field: &str) -> Result<(), FenError> {
+        self.castling_rights = CastlingRights::none();
+        self.castling_files = CastlingFiles::STANDARD;
+        self.variant = Variant::Standard;
+
+        if field == "-" {
+            return Ok(());
+        }
+        if field.is_empty() {
+            return Err(FenError::InvalidCastlingRights);
+        }
+
+        let mut named_files = false;
+        for ch in field.chars() {
+            let color = match ch.is_ascii_uppercase() {
+                true => Color::White,
+                false => Color::Black,
+            };
+            let king_file = self.home_king_file(color);
+
+            let (side, rook_file) = match ch.to_ascii_lowercase() {
+                'k' => (
+                    CastlingSide::Kingside,
+                    self.implied_rook_file(color, CastlingSide::Kingside, king_file),
+                ),
+                'q' => (
+                    CastlingSide::Queenside,
+                    self.implied_rook_file(color, CastlingSide::Queenside, king_file),
+                ),
+                letter @ 'a'..='h' => {
+                    let file = letter as u8 - b'a';
+                    named_files = true;
+                    match file.cmp(&king_file) {
+                        core::cmp::Ordering::Greater => (CastlingSide::Kingside, file),
+                        core::cmp::Ordering::Less => (CastlingSide::Queenside, file),
+                        core::cmp::Ordering::Equal => {
+                            return Err(FenError::InvalidCastlingRights);
+                        }
+                    }
+                }
+                _ => return Err(FenError::InvalidCastlingRights),
+            };
+
+            if self.castling_rights.can_castle(color, side) {
+                return Err(FenError::InvalidCastlingRights);
+            }
+            self.castling_rights.add(color, side);
+            self.castling_files.king[color.index()] = king_file;
+            self.castling_files.rooks[color.index()][side_index(side)] = rook_file;
+        }
+
+        if named_files || self.castling_files != CastlingFiles::STANDARD {
+            self.variant = Variant::Chess960;
+        }
+
+        Ok(())
+    }
+
+    /// The FEN castling field. Standard boards always write `KQkq` style,
+    /// Chess960 boards use X-FEN: `K` or `Q` where that reads back as the
+    /// same rook, the rook's file letter where it would not.
+    pub(crate) fn castling_fen(&self) -> String {
+        if self.variant == Variant::Standard {
+            return self.castling_rights.to_fen();
+        }
+
+        let mut field = String::new();
+        for color in Color::ALL {
+            let king_file = self.castling_files.king[color.index()];
+            for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+                if !self.castling_rights.can_castle(color, side) {
+                    continue;
+                }
+
+                let rook_file = self.castling_files.rooks[color.index()][side_index(side)];
+                let letter = match side {
+                    _ if rook_file != self.implied_rook_file(color, side, king_file) => {
+                        (b'a' + rook_file) as char
+                    }
+                    CastlingSide::Kingside => 'k',
+                    CastlingSide::Queenside => 'q',
+                };
+                field.push(match color {
+                    Color::White => letter.to_ascii_uppercase(),
+                    Color::Black => letter,
+                });
+            }
+        }
+
+        if field.is_empty() {
+            field.push('-');
+        }
+        field
+    }
+
+    // The file of `color`'s king on its back rank, the e-file if it is not
+    // there and so cannot castle anyway
+    fn home_king_file(&self, color: Color) -> u8 {
+        let kings = self.piece_bitboards[color.index()][PieceType::King.index()]
+            & Bitboard::rank(back_rank(color));
+        kings.first_square().map_or(4, |king| king.file())
+    }
+
+    // The rook `K` or `Q` stands for with the king on `king_file`: the
+    // outermost one on that side, or the corner square if there is none
+    fn implied_rook_file(&self, color: Color, side: CastlingSide, king_file: u8) -> u8 {
+        let corner = match side {
+            CastlingSide::Kingside => 7,
+            CastlingSide::Queenside => 0,
+        };
+        let rank = back_rank(color);
+        let rooks =
+            self.piece_bitboards[color.index()][PieceType::Rook.index()] & Bitboard::rank(rank);
+        let outermost = match side {
+            CastlingSide::Kingside => (rooks
+                & !rank_span(
+                    Square::from_coords(0, rank),
+                    Square::from_coords(king_file, rank),
+                ))
+            .last_square(),
+            CastlingSide::Queenside => (rooks
+                & !rank_span(
+                    Square::from_coords(king_file, rank),
+                    Square::from_coords(7, rank),
+                ))
+            .first_square(),
+        };
+        outermost.map_or(corner, |rook| rook.file())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::board::Board;
+use crate::types::{Color, Piece, PieceType, Square};
+use alloc::string::String;
+
+impl Board {
+    /// The board drawn with chess glyphs, white at the bottom. Empty
+    /// squares are blank and there are no coordinates, so the output is
+    /// just the pieces, one rank per line.
+    pub fn to_unicode(&self) -> String {
+        self.to_unicode_from(Color::White)
+    }
+
+    /// Like `to_unicode`, with `perspective`'s pieces at the bottom.
+    pub fn to_unicode_from(&self, perspective: Color) -> String {
+        let mut text = String::new();
+
+        for row in 0..8 {
+            let rank = match perspective {
+                Color::White => 7 - row,
+                Color::Black => row,
+            };
+            for col in 0..8 {
+                let file = match perspective {
+                    Color::White => col,
+                    Color::Black => 7 - col,
+                };
+                if col > 0 {
+                    text.push(' ');
+                }
+                text.push(match self.piece_at(Square::from_coords(file, rank)) {
+                    Some(piece) => unicode_glyph(piece),
+                    None => ' ',
+                });
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+fn unicode_glyph(piece: Piece) -> char {
+    match (piece.color, piece.piece_type) {
+        (Color::White, PieceType::King) => '♔',
+        (Color::White, PieceType::Queen) => '♕',
+        (Color::White, PieceType::Rook) => '♖',
+        (Color::White, PieceType::Bishop) => '♗',
+        (Color::White, PieceType::Knight) => '♘',
+        (Color::White, PieceType::Pawn) => '♙',
+        (Color::Black, PieceType::King) => '♚',
+        (Color::Black, PieceType::Queen) => '♛',
+        (Color::Black, PieceType::Rook) => '♜',
+        (Color::Black, PieceType::Bishop) => '♝',
+        (Color::Black, PieceType::Knight) => '♞',
+        (Color::Black, PieceType::Pawn) => '♟',
+    }
+}
+
+impl core::fmt::Display for Board {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for rank in (0..8).rev() {
+            write!(f, "{}", rank + 1)?;
+            for file in 0..8 {
+                let ch = match self.piece_at(Square::from_coords(file, rank)) {
+                    Some(piece) => piece.to_fen_char(),
+                    None => '.',
+                };
+                write!(f, " {}", ch)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "  a b c d e f g h")?;
+
+        let side = match self.side_to_move {
+            Color::White => "White",
+            Color::Black => "Black",
+        };
+        write!(
+            f,
+            "{} to move, castling {}, en passant ",
+            side,
+            self.castling_fen()
+        )?;
+        match self.en_passant_square {
+            Some(square) => write!(f, "{}", square),
+            None => write!(f, "-"),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::board::Board;
+use crate::types::{Color, PieceType};
+
+impl Board {
+    /// `phase` with all the starting pieces on the board.
+    pub const MAX_PHASE: u8 = 24;
+
+    /// White's material minus black's, in centipawns.
+    pub fn material_balance(&self) -> i32 {
+        let mut balance = 0;
+
+        for piece_type in PieceType::ALL {
+            let white = self.piece_bitboards[Color::White.index()][piece_type.index()];
+            let black = self.piece_bitboards[Color::Black.index()][piece_type.index()];
+            balance +=
+                (white.count_pieces() as i32 - black.count_pieces() as i32) * piece_type.value();
+        }
+
+        balance
+    }
+
+    /// How far from the endgame the position is, from 0 with only kings
+    /// and pawns left up to `MAX_PHASE` with every piece still on. Knights
+    /// and bishops count 1, rooks 2 and queens 4, for both sides. Extra
+    /// pieces from promotions cannot take it past `MAX_PHASE`.
+    pub fn phase(&self) -> u8 {
+        let mut phase = 0;
+
+        for color in Color::ALL {
+            for (piece_type, weight) in [
+                (PieceType::Knight, 1),
+                (PieceType::Bishop, 1),
+                (PieceType::Rook, 2),
+                (PieceType::Queen, 4),
+            ] {
+                phase += self.count(color, piece_type) * weight;
+            }
+        }
+
+        phase.min(Self::MAX_PHASE as u32) as u8
+    }
+}
@@ -1,10 +1,188 @@
+use crate::types::{Color, Piece, Square};
 use crate::{FenError, board::Board};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
 impl Board {
     pub fn from_fen(fen: &str) -> Result<Self, FenError> {
-        unimplemented!()
+        Self::from_fen_verbose(fen).map_err(|(error, _)| error)
+    }
+
+    /// Like `from_fen`, but also says where parsing failed: the byte
+    /// offset into `fen` of the start of the offending field, or of the
+    /// end of the string when fields are missing.
+    pub fn from_fen_verbose(fen: &str) -> Result<Self, (FenError, usize)> {
+        let fields = split_fields(fen);
+        // The move counters are optional, many test suites leave them off
+        if fields.len() < 4 {
+            return Err((FenError::InvalidFormat, fen.len()));
+        }
+        if fields.len() > 6 {
+            return Err((FenError::InvalidFormat, fields[6].0));
+        }
+        let at = |i: usize| fields[i].0;
+
+        let mut board = Board::new();
+        board
+            .parse_piece_placement(fields[0].1)
+            .map_err(|e| (e, at(0)))?;
+
+        board.side_to_move = match fields[1].1 {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err((FenError::InvalidActiveColor, at(1))),
+        };
+
+        board.parse_castling(fields[2].1).map_err(|e| (e, at(2)))?;
+        board.en_passant_square =
+            parse_en_passant(fields[3].1, board.side_to_move).map_err(|e| (e, at(3)))?;
+
+        board.halfmove_clock = match fields.get(4) {
+            Some((_, field)) => field
+                .parse()
+                .map_err(|_| (FenError::InvalidHalfMove, at(4)))?,
+            None => 0,
+        };
+        board.fullmove_number = match fields.get(5) {
+            Some((_, field)) => field
+                .parse()
+                .map_err(|_| (FenError::InvalidFullMove, at(5)))?,
+            None => 1,
+        };
+
+        board.position_hash = board.compute_hash();
+
+        Ok(board)
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match self.piece_at(Square::from_coords(file, rank)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece.to_fen_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+        fen.push(match self.side_to_move {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        fen.push(' ');
+        fen.push_str(&self.castling_fen());
+
+        fen.push(' ');
+        match self.en_passant_square {
+            Some(square) => fen.push_str(&square.to_string()),
+            None => fen.push('-'),
+        }
+
+        fen.push_str(&format!(
+            " {} {}",
+            self.halfmove_clock, self.fullmove_number
+        ));
+
+        fen
     }
 
     fn parse_piece_placement(&mut self, placement: &str) -> Result<(), FenError> {
-        unimplemented!()
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::InvalidPiecePlacement);
+        }
+
+        // FEN lists rank 8 first
+        for (i, rank_str) in ranks.iter().enumerate() {
+            let rank = 7 - i as u8;
+            let mut file = 0u8;
+
+            for ch in rank_str.chars() {
+                if let Some(skip) = ch.to_digit(10) {
+                    if skip == 0 || skip > 8 {
+                        return Err(FenError::InvalidPiecePlacement);
+                    }
+                    file += skip as u8;
+                } else {
+                    if file >= 8 {
+                        return Err(FenError::InvalidPiecePlacement);
+                    }
+                    let piece = Piece::from_fen_char(ch)?;
+                    self.place_piece(Square::from_coords(file, rank), piece);
+                    file += 1;
+                }
+
+                if file > 8 {
+                    return Err(FenError::InvalidPiecePlacement);
+                }
+            }
+
+            if file != 8 {
+                return Err(FenError::InvalidPiecePlacement);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Whitespace separated fields with the byte offset each starts at
+fn split_fields(fen: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+
+    for (i, ch) in fen.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (true, Some(s)) => {
+                fields.push((s, &fen[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
     }
+    if let Some(s) = start {
+        fields.push((s, &fen[s..]));
+    }
+
+    fields
+}
+
+fn parse_en_passant(field: &str, side_to_move: Color) -> Result<Option<Square>, FenError> {
+    if field == "-" {
+        return Ok(None);
+    }
+
+    let square = Square::from_algebraic(field).map_err(|_| FenError::InvalidEnPassant)?;
+    // The target square is always behind a pawn the other side just double
+    // pushed, so on the sixth rank with white to move and the third with black
+    let rank = match side_to_move {
+        Color::White => 5,
+        Color::Black => 2,
+    };
+    if square.rank() != rank {
+        return Err(FenError::InvalidEnPassant);
+    }
+
+    Ok(Some(square))
 }
@@ -0,0 +1,189 @@
+use crate::board::castling::castling_side;
+use crate::board::{Board, zobrist_castling_hash, zobrist_ep_hash, zobrist_side_hash};
+use crate::types::{CastlingRights, Color, Move, MoveFlag, Piece, PieceType, Square};
+
+/// The state `make_move` cannot recover from the move alone.
+#[derive(Clone, Copy, Debug)]
+pub struct Undo {
+    pub captured: Option<Piece>,
+    pub castling_rights: CastlingRights,
+    pub en_passant_square: Option<Square>,
+    pub halfmove_clock: u16,
+    pub fullmove_number: u16,
+    pub position_hash: u64,
+}
+
+/// The state `make_null_move` changes beyond the side to move.
+#[derive(Clone, Copy, Debug)]
+pub struct NullUndo {
+    pub en_passant_square: Option<Square>,
+    pub halfmove_clock: u16,
+    pub position_hash: u64,
+}
+
+impl Board {
+    /// Plays `mv` on the board. The move is assumed to be at least
+    /// pseudo-legal for the side to move. Pass the returned `Undo` to
+    /// `unmake_move` to take the move back.
+    pub fn make_move(&mut self, mv: Move) -> Undo {
+        let undo_base = Undo {
+            captured: None,
+            castling_rights: self.castling_rights,
+            en_passant_square: self.en_passant_square,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            position_hash: self.position_hash,
+        };
+
+        let from = mv.from();
+        let to = mv.to();
+        let us = self.side_to_move;
+
+        self.history.push(self.position_hash);
+
+        // Pieces are hashed by place/remove, the rest is swapped out here
+        // and back in once the new state is known
+        self.position_hash ^= zobrist_castling_hash(self.castling_rights);
+        if let Some(ep) = self.en_passant_square {
+            self.position_hash ^= zobrist_ep_hash(ep.file());
+        }
+
+        let piece = self
+            .piece_at(from)
+            .expect("make_move called with an empty origin square");
+
+        let captured = if mv.is_castle() {
+            self.move_castling_pieces(us, castling_side(mv), false);
+            None
+        } else {
+            self.remove_piece(from);
+            let captured = match mv.flag() {
+                // The captured pawn sits beside the mover, not on the target
+                MoveFlag::EnPassant => {
+                    self.remove_piece(Square::from_coords(to.file(), from.rank()))
+                }
+                _ => self.remove_piece(to),
+            };
+
+            let placed = match mv.promotion_piece() {
+                Some(piece_type) => Piece {
+                    piece_type,
+                    color: us,
+                },
+                None => piece,
+            };
+            self.place_piece(to, placed);
+            captured
+        };
+
+        self.update_castling_rights(from);
+        self.update_castling_rights(to);
+
+        self.en_passant_square = match mv.flag() {
+            MoveFlag::DoublePawnPush => Some(Square::ep_target(to, self.side_to_move)),
+            _ => None,
+        };
+
+        self.position_hash ^= zobrist_castling_hash(self.castling_rights);
+        if let Some(ep) = self.en_passant_square {
+            self.position_hash ^= zobrist_ep_hash(ep.file());
+        }
+        self.position_hash ^= zobrist_side_hash();
+
+        if matches!(piece.piece_type, PieceType::Pawn) || captured.is_some() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock = self.halfmove_clock.saturating_add(1);
+        }
+
+        if matches!(us, Color::Black) {
+            self.fullmove_number = self.fullmove_number.saturating_add(1);
+        }
+
+        self.side_to_move = us.opposite();
+
+        Undo {
+            captured,
+            ..undo_base
+        }
+    }
+
+    /// Reverses `make_move`. `mv` and `undo` must be the last move made
+    /// and the record it returned.
+    pub fn unmake_move(&mut self, mv: Move, undo: Undo) {
+        let us = self.side_to_move.opposite();
+        self.side_to_move = us;
+
+        if mv.is_castle() {
+            self.move_castling_pieces(us, castling_side(mv), true);
+        } else {
+            self.unmove_piece(mv, us, undo.captured);
+        }
+
+        self.castling_rights = undo.castling_rights;
+        self.en_passant_square = undo.en_passant_square;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.position_hash = undo.position_hash;
+        self.history.pop();
+    }
+
+    // Puts the piece `mv` moved back, and whatever it captured
+    fn unmove_piece(&mut self, mv: Move, us: Color, captured: Option<Piece>) {
+        let from = mv.from();
+        let to = mv.to();
+
+        let moved = self
+            .remove_piece(to)
+            .expect("unmake_move called with an empty target square");
+        let original = match mv.promotion_piece() {
+            Some(_) => Piece {
+                piece_type: PieceType::Pawn,
+                color: us,
+            },
+            None => moved,
+        };
+        self.place_piece(from, original);
+
+        if let Some(captured) = captured {
+            let capture_square = match mv.flag() {
+                MoveFlag::EnPassant => Square::from_coords(to.file(), from.rank()),
+                _ => to,
+            };
+            self.place_piece(capture_square, captured);
+        }
+    }
+
+    /// Passes the turn without moving, for null move pruning. Any en
+    /// passant chance is gone and the halfmove clock still ticks, as after
+    /// a real quiet move. Passing while in check would let the king be
+    /// captured, so callers must not do it then. Pass the returned
+    /// `NullUndo` to `unmake_null_move` to take it back.
+    pub fn make_null_move(&mut self) -> NullUndo {
+        let undo = NullUndo {
+            en_passant_square: self.en_passant_square,
+            halfmove_clock: self.halfmove_clock,
+            position_hash: self.position_hash,
+        };
+
+        self.history.push(self.position_hash);
+
+        if let Some(ep) = self.en_passant_square.take() {
+            self.position_hash ^= zobrist_ep_hash(ep.file());
+        }
+        self.halfmove_clock = self.halfmove_clock.saturating_add(1);
+        self.side_to_move = self.side_to_move.opposite();
+        self.position_hash ^= zobrist_side_hash();
+
+        undo
+    }
+
+    /// Reverses `make_null_move`, which must be the last move made.
+    pub fn unmake_null_move(&mut self, undo: NullUndo) {
+        self.side_to_move = self.side_to_move.opposite();
+        self.en_passant_square = undo.en_passant_square;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.position_hash = undo.position_hash;
+        self.history.pop();
+    }
+}
@@ -4,11 +4,42 @@ use crate::bitboard::Bitboard;
 use crate::types::Color;
 use crate::types::Square;
 use crate::types::castling::CastlingRights;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
 
 mod zobrist;
-use zobrist::zobrist_piece_hash;
+pub use zobrist::ZobristKeys;
+use zobrist::{zobrist_castling_hash, zobrist_ep_hash, zobrist_piece_hash, zobrist_side_hash};
 
+mod attacks;
+mod builder;
+pub use builder::BoardBuilder;
+mod castling;
+use castling::CastlingFiles;
+pub use castling::Variant;
+mod display;
+mod eval;
 mod fen;
+mod make_move;
+pub use make_move::{NullUndo, Undo};
+
+mod movegen;
+mod pawns;
+mod perft;
+mod psqt;
+pub use psqt::PieceSquareTable;
+mod san;
+pub use san::SanError;
+mod see;
+#[cfg(feature = "serde")]
+mod serialize;
+mod status;
+pub use status::GameStatus;
+
+mod uci;
+pub use uci::{MoveParseError, UciError};
+mod validate;
+pub use validate::PositionError;
 
 #[derive(Clone, Debug)]
 pub struct Board {
@@ -22,12 +53,17 @@ pub struct Board {
     halfmove_clock: u16,
     fullmove_number: u16,
 
+    variant: Variant,
+    castling_files: CastlingFiles,
+
     position_hash: u64,
+    // Hashes of every earlier position, most recent last
+    history: Vec<u64>,
 }
 
 impl Board {
     pub fn new() -> Self {
-        Self {
+        let mut board = Self {
             piece_bitboards: [[Bitboard::EMPTY; 6]; 2],
             color_bitboard: [Bitboard::EMPTY; 2],
             all_pieces: Bitboard::EMPTY,
@@ -36,26 +72,110 @@ impl Board {
             en_passant_square: None,
             halfmove_clock: 0,
             fullmove_number: 1,
+            variant: Variant::Standard,
+            castling_files: CastlingFiles::STANDARD,
             position_hash: 0,
+            history: Vec::new(),
+        };
+        board.position_hash = board.compute_hash();
+        board
+    }
+
+    /// The standard initial position, white to move.
+    pub fn starting_position() -> Self {
+        const BACK_RANK: [PieceType; 8] = [
+            PieceType::Rook,
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Queen,
+            PieceType::King,
+            PieceType::Bishop,
+            PieceType::Knight,
+            PieceType::Rook,
+        ];
+
+        let mut board = Self::new();
+
+        for (file, &piece_type) in BACK_RANK.iter().enumerate() {
+            let file = file as u8;
+            for (color, back_rank, pawn_rank) in [(Color::White, 0, 1), (Color::Black, 7, 6)] {
+                board.place_piece(
+                    Square::from_coords(file, back_rank),
+                    Piece { piece_type, color },
+                );
+                board.place_piece(
+                    Square::from_coords(file, pawn_rank),
+                    Piece {
+                        piece_type: PieceType::Pawn,
+                        color,
+                    },
+                );
+            }
+        }
+
+        board
+    }
+
+    /// The same position seen from the other side: ranks mirrored, colors
+    /// swapped, the other side to move. An evaluation should score the
+    /// flipped board as the negation of the original. The move counters
+    /// are kept, the history is not since its hashes no longer apply.
+    pub fn flip(&self) -> Board {
+        let mut flipped = Board::new();
+
+        for color in Color::ALL {
+            let from = color.opposite().index();
+            for piece_type in PieceType::ALL {
+                flipped.piece_bitboards[color.index()][piece_type.index()] =
+                    self.piece_bitboards[from][piece_type.index()].flip_vertical();
+            }
+            flipped.color_bitboard[color.index()] = self.color_bitboard[from].flip_vertical();
         }
+        flipped.all_pieces = self.all_pieces.flip_vertical();
+
+        let rights = self.castling_rights;
+        flipped.castling_rights = CastlingRights {
+            white_kingside: rights.black_kingside,
+            white_queenside: rights.black_queenside,
+            black_kingside: rights.white_kingside,
+            black_queenside: rights.white_queenside,
+        };
+        flipped.variant = self.variant;
+        flipped.castling_files = self.castling_files.flip();
+        flipped.side_to_move = self.side_to_move.opposite();
+        flipped.en_passant_square = self.en_passant_square.map(Square::flip_vertical);
+        flipped.halfmove_clock = self.halfmove_clock;
+        flipped.fullmove_number = self.fullmove_number;
+        flipped.position_hash = flipped.compute_hash();
+
+        flipped
     }
 
+    /// The position as the side to move sees it, always with white to
+    /// move: the flipped board when black is to move, a plain copy when
+    /// white is. An evaluation written from white's point of view scores
+    /// either side through this.
+    pub fn side_to_move_perspective(&self) -> Board {
+        match self.side_to_move {
+            Color::White => self.clone(),
+            Color::Black => self.flip(),
+        }
+    }
+
+    #[deprecated(note = "misspelled, use `piece_at` instead")]
     pub fn peice_at(&self, square: Square) -> Option<Piece> {
+        self.piece_at(square)
+    }
+
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
         if !self.all_pieces.is_set(square) {
             return None;
         }
 
-        for color in [Color::White, Color::Black] {
-            if self.color_bitboard[color as usize].is_set(square) {
-                for piece_type in [
-                    PieceType::Pawn,
-                    PieceType::Knight,
-                    PieceType::Bishop,
-                    PieceType::Rook,
-                    PieceType::Queen,
-                    PieceType::King,
-                ] {
-                    if self.piece_bitboards[color as usize][piece_type as usize].is_set(square) {
+        for color in Color::ALL {
+            if self.color_bitboard[color.index()].is_set(square) {
+                for piece_type in PieceType::ALL {
+                    if self.piece_bitboards[color.index()][piece_type.index()].is_set(square) {
                         return Some(Piece { piece_type, color });
                     }
                 }
@@ -64,15 +184,102 @@ impl Board {
         None
     }
 
+    /// Every piece of one color and type.
+    pub fn pieces(&self, color: Color, piece_type: PieceType) -> Bitboard {
+        self.piece_bitboards[color.index()][piece_type.index()]
+    }
+
+    /// How many pieces of one color and type are on the board.
+    pub fn count(&self, color: Color, piece_type: PieceType) -> u32 {
+        self.pieces(color, piece_type).count_pieces()
+    }
+
+    /// How many pieces of either color are on the board, kings included.
+    pub fn piece_count(&self) -> u32 {
+        self.all_pieces.count_pieces()
+    }
+
+    /// Where the king of `color` stands. Every legal position has exactly
+    /// one king per side, so this does not return an `Option`.
+    pub fn king_square(&self, color: Color) -> Square {
+        let kings = self.piece_bitboards[color.index()][PieceType::King.index()];
+        debug_assert_eq!(
+            kings.count_pieces(),
+            1,
+            "{color:?} must have exactly one king"
+        );
+        Square::from_index(kings.bsf() as usize)
+    }
+
+    /// The square a pawn just skipped over with a double push.
+    ///
+    /// This is set after every double push, whether or not an enemy pawn
+    /// stands ready to capture, the same as FEN records it. The hash
+    /// includes it too, so 1.Nf3 Nf6 2.e4 and 1.e4 Nf6 2.Nf3 hash
+    /// differently even though black cannot capture en passant in either.
+    pub fn en_passant_square(&self) -> Option<Square> {
+        self.en_passant_square
+    }
+
+    /// Every occupied square.
+    pub fn occupancy(&self) -> Bitboard {
+        self.all_pieces
+    }
+
+    /// Every square holding a piece of `color`.
+    pub fn occupancy_of(&self, color: Color) -> Bitboard {
+        self.color_bitboard[color.index()]
+    }
+
+    /// Every piece on the board with its square, from a1 to h8.
+    pub fn pieces_iter(&self) -> impl Iterator<Item = (Square, Piece)> + '_ {
+        // Iterating a Bitboard pops squares off a copy, lowest first
+        self.all_pieces
+            .filter_map(|square| Some((square, self.piece_at(square)?)))
+    }
+
     pub fn place_piece(&mut self, square: Square, piece: Piece) {
-        self.piece_bitboards[piece.color as usize][piece.piece_type as usize].set(square);
+        self.piece_bitboards[piece.color.index()][piece.piece_type.index()].set(square);
 
-        self.color_bitboard[piece.color as usize].set(square);
+        self.color_bitboard[piece.color.index()].set(square);
 
         self.all_pieces.set(square);
 
         self.position_hash ^= zobrist_piece_hash(square, piece);
     }
+
+    pub fn remove_piece(&mut self, square: Square) -> Option<Piece> {
+        let piece = self.piece_at(square)?;
+
+        self.piece_bitboards[piece.color.index()][piece.piece_type.index()].clear(square);
+
+        self.color_bitboard[piece.color.index()].clear(square);
+
+        self.all_pieces.clear(square);
+
+        self.position_hash ^= zobrist_piece_hash(square, piece);
+
+        Some(piece)
+    }
+
+    /// Whether the current position has occurred at least twice before
+    /// with the same side to move. Only positions since the last pawn move
+    /// or capture are considered, nothing before that can repeat.
+    pub fn is_threefold_repetition(&self) -> bool {
+        let window = (self.halfmove_clock as usize).min(self.history.len());
+
+        let repeats = self
+            .history
+            .iter()
+            .rev()
+            .take(window)
+            .skip(1)
+            .step_by(2)
+            .filter(|&&hash| hash == self.repetition_key())
+            .count();
+
+        repeats >= 2
+    }
 }
 
 impl Default for Board {
@@ -81,12 +288,36 @@ impl Default for Board {
     }
 }
 
+/// Boards are equal when they are the same position for repetition
+/// purposes: placement, side to move, castling rights (with the rooks
+/// they apply to) and en passant square. The move counters and history
+/// are ignored, as in the hash.
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.piece_bitboards == other.piece_bitboards
+            && self.side_to_move == other.side_to_move
+            && self.castling_rights == other.castling_rights
+            && self.castling_files == other.castling_files
+            && self.en_passant_square == other.en_passant_square
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    // Equal boards have equal Zobrist hashes, so that is all it needs
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.position_hash);
+    }
+}
+
 #[derive(Debug)]
 pub enum FenError {
     InvalidFormat,
     InvalidPiecePlacement,
     InvalidActiveColor,
     InvalidCastlingRights,
+    InvalidEnPassant,
     InvalidPiece,
     InvalidHalfMove,
     InvalidFullMove,
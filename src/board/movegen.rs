@@ -0,0 +1,250 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::board::castling::{castling_side, rank_span};
+use crate::magic::AttackTable;
+use crate::types::{CastlingSide, Color, Direction, Move, MoveFlag, MoveList, PieceType, Square};
+use alloc::vec::Vec;
+
+const PROMOTION_PIECES: [PieceType; 4] = [
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+];
+
+impl Board {
+    /// Every move for the side to move that obeys piece movement rules.
+    /// These may still leave the mover's own king in check.
+    pub fn generate_pseudo_legal(&self, attacks: &AttackTable) -> Vec<Move> {
+        let mut list = MoveList::new();
+        self.generate_pseudo_legal_into(&mut list, attacks);
+        list.as_slice().to_vec()
+    }
+
+    /// Pseudo-legal moves minus those that leave the mover's king in check
+    /// or castle out of, through, or into check.
+    pub fn generate_legal(&self, attacks: &AttackTable) -> Vec<Move> {
+        let mut list = MoveList::new();
+        self.generate_legal_into(&mut list, attacks);
+        list.as_slice().to_vec()
+    }
+
+    /// `generate_legal` into a reusable buffer, without allocating. `list`
+    /// is cleared first.
+    pub fn generate_legal_into(&self, list: &mut MoveList, attacks: &AttackTable) {
+        self.generate_pseudo_legal_into(list, attacks);
+        self.retain_legal(list, attacks);
+    }
+
+    /// Legal captures, en passant included, and promotions. The noisy moves
+    /// a quiescence search looks at, generated without the quiet ones.
+    pub fn generate_captures(&self, attacks: &AttackTable) -> Vec<Move> {
+        let mut list = MoveList::new();
+
+        let targets = self.color_bitboard[self.side_to_move.opposite().index()];
+        self.generate_pawn_moves(attacks, Bitboard::FULL, true, &mut list);
+        self.generate_piece_moves(attacks, Bitboard::FULL, targets, &mut list);
+
+        self.retain_legal(&mut list, attacks);
+        list.as_slice().to_vec()
+    }
+
+    fn generate_pseudo_legal_into(&self, list: &mut MoveList, attacks: &AttackTable) {
+        list.clear();
+
+        let targets = !self.color_bitboard[self.side_to_move.index()];
+        self.generate_pawn_moves(attacks, Bitboard::FULL, false, list);
+        self.generate_piece_moves(attacks, Bitboard::FULL, targets, list);
+        self.generate_castling_moves(list);
+    }
+
+    /// Whether `mv` is legal in this position, checked for that one move
+    /// without generating the rest. Moves from a GUI or a transposition
+    /// table can be anything, so nothing about `mv` is assumed.
+    pub fn is_legal(&self, mv: Move, attacks: &AttackTable) -> bool {
+        let Some(piece) = self.piece_at(mv.from()) else {
+            return false;
+        };
+        if piece.color != self.side_to_move {
+            return false;
+        }
+
+        // Everything the moving piece can do, at most a few dozen moves
+        let mut list = MoveList::new();
+        let source = Bitboard::from_square(mv.from());
+        match piece.piece_type {
+            PieceType::Pawn => self.generate_pawn_moves(attacks, source, false, &mut list),
+            PieceType::King if mv.is_castle() => self.generate_castling_moves(&mut list),
+            _ => {
+                let targets = !self.color_bitboard[self.side_to_move.index()];
+                self.generate_piece_moves(attacks, source, targets, &mut list);
+            }
+        }
+
+        list.contains(&mv) && self.passes_legality_checks(mv, attacks)
+    }
+
+    fn retain_legal(&self, list: &mut MoveList, attacks: &AttackTable) {
+        list.retain(|mv| self.passes_legality_checks(mv, attacks));
+    }
+
+    // The checks `generate_legal` applies on top of pseudo-legality
+    fn passes_legality_checks(&self, mv: Move, attacks: &AttackTable) -> bool {
+        // The king may not castle out of or through check, where it lands
+        // is left to the usual test below
+        if mv.is_castle() {
+            let them = self.side_to_move.opposite();
+            let crossed = rank_span(mv.from(), mv.to()) & !Bitboard::from_square(mv.to());
+            for square in crossed {
+                if self.is_square_attacked(square, them, attacks) {
+                    return false;
+                }
+            }
+        }
+
+        self.king_safe_after(mv, attacks)
+    }
+
+    // Whether the mover's king is out of check once `mv` is played, worked
+    // out from the occupancy after the move rather than by making it
+    fn king_safe_after(&self, mv: Move, attacks: &AttackTable) -> bool {
+        let us = self.side_to_move;
+        let them = us.opposite();
+        let from = mv.from();
+        let to = mv.to();
+
+        let Some(king) = self.piece_bitboards[us.index()][PieceType::King.index()].first_square()
+        else {
+            return true;
+        };
+        let king = if king == from { to } else { king };
+
+        let mut captured = Bitboard::from_square(to);
+        if mv.flag() == MoveFlag::EnPassant {
+            captured.set(Square::from_coords(to.file(), from.rank()));
+        }
+        let mut occupied = self.all_pieces & !captured & !Bitboard::from_square(from);
+        if mv.is_castle() {
+            let rook = self.castling_squares(us, castling_side(mv));
+            occupied.clear(rook.rook_from);
+            occupied.set(rook.rook_to);
+        }
+        occupied.set(to);
+
+        let pieces = &self.piece_bitboards[them.index()];
+        let enemy = |piece_type: PieceType| pieces[piece_type.index()] & !captured;
+        let diagonal = enemy(PieceType::Bishop) | enemy(PieceType::Queen);
+        let orthogonal = enemy(PieceType::Rook) | enemy(PieceType::Queen);
+
+        (attacks.pawn_attacks(king, us) & enemy(PieceType::Pawn)).is_empty()
+            && (attacks.knight_attacks(king) & enemy(PieceType::Knight)).is_empty()
+            && (attacks.king_attacks(king) & enemy(PieceType::King)).is_empty()
+            && (attacks.bishop_attacks(king, occupied) & diagonal).is_empty()
+            && (attacks.rook_attacks(king, occupied) & orthogonal).is_empty()
+    }
+
+    // Moves of the pawns on `sources`. With `noisy_only` set, pushes are
+    // left out unless they promote
+    fn generate_pawn_moves(
+        &self,
+        attacks: &AttackTable,
+        sources: Bitboard,
+        noisy_only: bool,
+        moves: &mut MoveList,
+    ) {
+        let us = self.side_to_move;
+        let enemies = self.color_bitboard[us.opposite().index()];
+
+        let (forward, start_rank, promotion_rank) = match us {
+            Color::White => (Direction::NORTH, 1, 7),
+            Color::Black => (Direction::SOUTH, 6, 0),
+        };
+
+        for from in self.piece_bitboards[us.index()][PieceType::Pawn.index()] & sources {
+            if let Some(to) = from.offset(forward)
+                && !self.all_pieces.is_set(to)
+            {
+                if to.rank() == promotion_rank {
+                    for piece in PROMOTION_PIECES {
+                        moves.push(Move::promotion(from, to, piece));
+                    }
+                } else if !noisy_only {
+                    moves.push(Move::quiet(from, to));
+
+                    if from.rank() == start_rank
+                        && let Some(double) = to.offset(forward)
+                        && !self.all_pieces.is_set(double)
+                    {
+                        moves.push(Move::double_pawn_push(from, double));
+                    }
+                }
+            }
+
+            let pawn_attacks = attacks.pawn_attacks(from, us);
+
+            for to in pawn_attacks & enemies {
+                if to.rank() == promotion_rank {
+                    for piece in PROMOTION_PIECES {
+                        moves.push(Move::promotion_capture(from, to, piece));
+                    }
+                } else {
+                    moves.push(Move::capture(from, to));
+                }
+            }
+
+            if let Some(ep) = self.en_passant_square
+                && pawn_attacks.is_set(ep)
+            {
+                moves.push(Move::en_passant(from, ep));
+            }
+        }
+    }
+
+    // Only moves from `sources` landing on `targets` are generated
+    fn generate_piece_moves(
+        &self,
+        attacks: &AttackTable,
+        sources: Bitboard,
+        targets: Bitboard,
+        moves: &mut MoveList,
+    ) {
+        let us = self.side_to_move;
+        let enemies = self.color_bitboard[us.opposite().index()];
+
+        for piece_type in [
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Rook,
+            PieceType::Queen,
+            PieceType::King,
+        ] {
+            for from in self.piece_bitboards[us.index()][piece_type.index()] & sources {
+                let reachable = match piece_type {
+                    PieceType::Knight => attacks.knight_attacks(from),
+                    PieceType::Bishop => attacks.bishop_attacks(from, self.all_pieces),
+                    PieceType::Rook => attacks.rook_attacks(from, self.all_pieces),
+                    PieceType::Queen => attacks.queen_attacks(from, self.all_pieces),
+                    PieceType::King => attacks.king_attacks(from),
+                    PieceType::Pawn => unreachable!(),
+                } & targets;
+
+                for to in reachable {
+                    if enemies.is_set(to) {
+                        moves.push(Move::capture(from, to));
+                    } else {
+                        moves.push(Move::quiet(from, to));
+                    }
+                }
+            }
+        }
+    }
+
+    fn generate_castling_moves(&self, moves: &mut MoveList) {
+        let us = self.side_to_move;
+        for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+            if let Some(mv) = self.castling_move(us, side) {
+                moves.push(mv);
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::types::{Color, Direction, PieceType};
+
+impl Board {
+    /// `color`'s pawns that share their file with another of its pawns,
+    /// both pawns of a doubled pair included.
+    pub fn doubled_pawns(&self, color: Color) -> Bitboard {
+        let pawns = self.pieces(color, PieceType::Pawn);
+        let others =
+            pawns.shift(Direction::NORTH).north_fill() | pawns.shift(Direction::SOUTH).south_fill();
+        pawns & others
+    }
+
+    /// `color`'s pawns with no pawn of their own on either adjacent file.
+    pub fn isolated_pawns(&self, color: Color) -> Bitboard {
+        let pawns = self.pieces(color, PieceType::Pawn);
+        let files = pawns.file_fill();
+        let neighbours = files.shift(Direction::EAST) | files.shift(Direction::WEST);
+        pawns & !neighbours
+    }
+
+    /// `color`'s pawns with no enemy pawn ahead of them on their own file
+    /// or an adjacent one, so nothing can stop or capture them on the way.
+    pub fn passed_pawns(&self, color: Color) -> Bitboard {
+        let pawns = self.pieces(color, PieceType::Pawn);
+        let enemies = self.pieces(color.opposite(), PieceType::Pawn);
+
+        // Every square behind an enemy pawn, from its point of view ahead
+        let behind = match color {
+            Color::White => enemies.shift(Direction::SOUTH).south_fill(),
+            Color::Black => enemies.shift(Direction::NORTH).north_fill(),
+        };
+        let blocked = behind | behind.shift(Direction::EAST) | behind.shift(Direction::WEST);
+        pawns & !blocked
+    }
+}
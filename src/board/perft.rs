@@ -0,0 +1,46 @@
+use crate::board::Board;
+use crate::magic::AttackTable;
+use crate::types::{Move, MoveList};
+use alloc::vec::Vec;
+
+impl Board {
+    /// Counts the leaf nodes of the legal move tree `depth` plies deep.
+    pub fn perft(&mut self, depth: u32, attacks: &AttackTable) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let mut moves = MoveList::new();
+        self.generate_legal_into(&mut moves, attacks);
+        // Bulk count, no need to make the last ply
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for &mv in &moves {
+            let undo = self.make_move(mv);
+            nodes += self.perft(depth - 1, attacks);
+            self.unmake_move(mv, undo);
+        }
+        nodes
+    }
+
+    /// Perft split by root move, for narrowing down where a move
+    /// generator disagrees with a reference engine.
+    pub fn perft_divide(&mut self, depth: u32, attacks: &AttackTable) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+
+        let moves = self.generate_legal(attacks);
+        let mut divide = Vec::with_capacity(moves.len());
+
+        for mv in moves {
+            let undo = self.make_move(mv);
+            divide.push((mv, self.perft(depth - 1, attacks)));
+            self.unmake_move(mv, undo);
+        }
+        divide
+    }
+}
@@ -0,0 +1,121 @@
+use crate::board::Board;
+use crate::types::{Color, PieceType, Square};
+
+/// Positional bonuses in centipawns for each piece type on each square,
+/// on top of the material value.
+///
+/// Tables are indexed by `PieceType::index` and written the way the board
+/// looks from white's side, rank 8 first and a8 at index 0. Black uses the
+/// same tables mirrored, so one set describes both colors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PieceSquareTable {
+    pub tables: [[i32; 64]; 6],
+}
+
+impl PieceSquareTable {
+    /// The bonus for `color`'s `piece_type` standing on `square`.
+    pub fn value(&self, color: Color, piece_type: PieceType, square: Square) -> i32 {
+        // Index 0 is a8 for white, and a8 mirrored is a1 for black
+        let index = match color {
+            Color::White => square.flip_vertical().index(),
+            Color::Black => square.index(),
+        };
+        self.tables[piece_type.index()][index]
+    }
+}
+
+/// Tomasz Michniewski's tables from the "simplified evaluation function",
+/// with the middlegame king table.
+#[rustfmt::skip]
+impl Default for PieceSquareTable {
+    fn default() -> Self {
+        Self {
+            tables: [
+                // Pawn
+                [
+                     0,   0,   0,   0,   0,   0,   0,   0,
+                    50,  50,  50,  50,  50,  50,  50,  50,
+                    10,  10,  20,  30,  30,  20,  10,  10,
+                     5,   5,  10,  25,  25,  10,   5,   5,
+                     0,   0,   0,  20,  20,   0,   0,   0,
+                     5,  -5, -10,   0,   0, -10,  -5,   5,
+                     5,  10,  10, -20, -20,  10,  10,   5,
+                     0,   0,   0,   0,   0,   0,   0,   0,
+                ],
+                // Knight
+                [
+                   -50, -40, -30, -30, -30, -30, -40, -50,
+                   -40, -20,   0,   0,   0,   0, -20, -40,
+                   -30,   0,  10,  15,  15,  10,   0, -30,
+                   -30,   5,  15,  20,  20,  15,   5, -30,
+                   -30,   0,  15,  20,  20,  15,   0, -30,
+                   -30,   5,  10,  15,  15,  10,   5, -30,
+                   -40, -20,   0,   5,   5,   0, -20, -40,
+                   -50, -40, -30, -30, -30, -30, -40, -50,
+                ],
+                // Bishop
+                [
+                   -20, -10, -10, -10, -10, -10, -10, -20,
+                   -10,   0,   0,   0,   0,   0,   0, -10,
+                   -10,   0,   5,  10,  10,   5,   0, -10,
+                   -10,   5,   5,  10,  10,   5,   5, -10,
+                   -10,   0,  10,  10,  10,  10,   0, -10,
+                   -10,  10,  10,  10,  10,  10,  10, -10,
+                   -10,   5,   0,   0,   0,   0,   5, -10,
+                   -20, -10, -10, -10, -10, -10, -10, -20,
+                ],
+                // Rook
+                [
+                     0,   0,   0,   0,   0,   0,   0,   0,
+                     5,  10,  10,  10,  10,  10,  10,   5,
+                    -5,   0,   0,   0,   0,   0,   0,  -5,
+                    -5,   0,   0,   0,   0,   0,   0,  -5,
+                    -5,   0,   0,   0,   0,   0,   0,  -5,
+                    -5,   0,   0,   0,   0,   0,   0,  -5,
+                    -5,   0,   0,   0,   0,   0,   0,  -5,
+                     0,   0,   0,   5,   5,   0,   0,   0,
+                ],
+                // Queen
+                [
+                   -20, -10, -10,  -5,  -5, -10, -10, -20,
+                   -10,   0,   0,   0,   0,   0,   0, -10,
+                   -10,   0,   5,   5,   5,   5,   0, -10,
+                    -5,   0,   5,   5,   5,   5,   0,  -5,
+                     0,   0,   5,   5,   5,   5,   0,  -5,
+                   -10,   5,   5,   5,   5,   5,   0, -10,
+                   -10,   0,   5,   0,   0,   0,   0, -10,
+                   -20, -10, -10,  -5,  -5, -10, -10, -20,
+                ],
+                // King
+                [
+                   -30, -40, -40, -50, -50, -40, -40, -30,
+                   -30, -40, -40, -50, -50, -40, -40, -30,
+                   -30, -40, -40, -50, -50, -40, -40, -30,
+                   -30, -40, -40, -50, -50, -40, -40, -30,
+                   -20, -30, -30, -40, -40, -30, -30, -20,
+                   -10, -20, -20, -20, -20, -20, -20, -10,
+                    20,  20,   0,   0,   0,   0,  20,  20,
+                    20,  30,  10,   0,   0,  10,  30,  20,
+                ],
+            ],
+        }
+    }
+}
+
+impl Board {
+    /// White's piece-square bonuses minus black's.
+    pub fn psqt_score(&self, tables: &PieceSquareTable) -> i32 {
+        let mut score = 0;
+
+        for piece_type in PieceType::ALL {
+            for square in self.pieces(Color::White, piece_type) {
+                score += tables.value(Color::White, piece_type, square);
+            }
+            for square in self.pieces(Color::Black, piece_type) {
+                score -= tables.value(Color::Black, piece_type, square);
+            }
+        }
+
+        score
+    }
+}
@@ -0,0 +1,183 @@
+use crate::board::Board;
+use crate::magic::AttackTable;
+use crate::types::{Move, MoveFlag, PieceType, Square};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SanError {
+    /// Not shaped like a SAN move at all.
+    InvalidFormat,
+    /// Well formed, but no legal move matches it.
+    IllegalMove,
+    /// More than one legal move matches, it needs disambiguating.
+    AmbiguousMove,
+}
+
+impl Board {
+    /// Standard algebraic notation for `mv`, e.g. "Nf3", "exd5", "O-O",
+    /// "e8=Q+" or "Rdf8". `mv` must be legal in this position. Only moves
+    /// that give check are played out, to tell mate from check.
+    pub fn move_to_san(&self, mv: Move, attacks: &AttackTable) -> String {
+        let mut san = match mv.flag() {
+            MoveFlag::KingCastle => String::from("O-O"),
+            MoveFlag::QueenCastle => String::from("O-O-O"),
+            _ => self.san_body(mv, attacks),
+        };
+
+        if self.gives_check(mv, attacks) {
+            let mut after = self.clone();
+            after.make_move(mv);
+            if after.generate_legal(attacks).is_empty() {
+                san.push('#');
+            } else {
+                san.push('+');
+            }
+        }
+
+        san
+    }
+
+    // Everything but castling and the check suffix
+    fn san_body(&self, mv: Move, attacks: &AttackTable) -> String {
+        let from = mv.from();
+        let to = mv.to();
+        let piece_type = self
+            .piece_at(from)
+            .expect("move_to_san called with an empty origin square")
+            .piece_type;
+
+        let mut san = String::new();
+
+        if matches!(piece_type, PieceType::Pawn) {
+            if mv.is_capture() {
+                san.push((b'a' + from.file()) as char);
+                san.push('x');
+            }
+            san.push_str(&to.to_string());
+
+            if let Some(promotion) = mv.promotion_piece() {
+                san.push('=');
+                san.push(promotion.to_char().to_ascii_uppercase());
+            }
+            return san;
+        }
+
+        san.push(piece_type.to_char().to_ascii_uppercase());
+
+        // Other pieces of the same type that could also go to `to`
+        let rivals: Vec<_> = self
+            .generate_legal(attacks)
+            .into_iter()
+            .filter(|other| other.to() == to && other.from() != from)
+            .filter(|other| {
+                self.piece_at(other.from())
+                    .is_some_and(|piece| piece.piece_type == piece_type)
+            })
+            .map(|other| other.from())
+            .collect();
+
+        if !rivals.is_empty() {
+            let shares_file = rivals.iter().any(|square| square.file() == from.file());
+            let shares_rank = rivals.iter().any(|square| square.rank() == from.rank());
+
+            if !shares_file {
+                san.push((b'a' + from.file()) as char);
+            } else if !shares_rank {
+                san.push((b'1' + from.rank()) as char);
+            } else {
+                san.push_str(&from.to_string());
+            }
+        }
+
+        if mv.is_capture() {
+            san.push('x');
+        }
+        san.push_str(&to.to_string());
+
+        san
+    }
+
+    /// Finds the legal move `san` describes. Check and annotation suffixes
+    /// are ignored, as are a missing or extra capture 'x', surplus
+    /// disambiguation, "0-0" style castling and promotions without '='.
+    pub fn san_to_move(&self, san: &str, attacks: &AttackTable) -> Result<Move, SanError> {
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+        let legal = self.generate_legal(attacks);
+
+        let castle = match san {
+            "O-O" | "0-0" => Some(MoveFlag::KingCastle),
+            "O-O-O" | "0-0-0" => Some(MoveFlag::QueenCastle),
+            _ => None,
+        };
+        if let Some(flag) = castle {
+            return legal
+                .into_iter()
+                .find(|mv| mv.flag() == flag)
+                .ok_or(SanError::IllegalMove);
+        }
+
+        let (body, promotion) = split_promotion(san)?;
+
+        let (piece_type, rest) = match body.chars().next() {
+            Some(ch @ ('N' | 'B' | 'R' | 'Q' | 'K')) => (PieceType::from_char(ch), &body[1..]),
+            _ => (Some(PieceType::Pawn), body),
+        };
+        let piece_type = piece_type.ok_or(SanError::InvalidFormat)?;
+
+        if rest.len() < 2 || !rest.is_ascii() {
+            return Err(SanError::InvalidFormat);
+        }
+        let (rest, target) = rest.split_at(rest.len() - 2);
+        let to = Square::from_algebraic(target).map_err(|_| SanError::InvalidFormat)?;
+        let rest = rest.strip_suffix('x').unwrap_or(rest);
+
+        // What is left can only be the origin file, rank, or both
+        let mut from_file = None;
+        let mut from_rank = None;
+        for byte in rest.bytes() {
+            match byte {
+                b'a'..=b'h' if from_file.is_none() && from_rank.is_none() => {
+                    from_file = Some(byte - b'a')
+                }
+                b'1'..=b'8' if from_rank.is_none() => from_rank = Some(byte - b'1'),
+                _ => return Err(SanError::InvalidFormat),
+            }
+        }
+
+        let candidates: Vec<Move> = legal
+            .into_iter()
+            .filter(|mv| {
+                mv.to() == to
+                    && mv.promotion_piece() == promotion
+                    && from_file.is_none_or(|file| mv.from().file() == file)
+                    && from_rank.is_none_or(|rank| mv.from().rank() == rank)
+                    && self
+                        .piece_at(mv.from())
+                        .is_some_and(|piece| piece.piece_type == piece_type)
+            })
+            .collect();
+
+        match candidates.as_slice() {
+            [mv] => Ok(*mv),
+            [] => Err(SanError::IllegalMove),
+            _ => Err(SanError::AmbiguousMove),
+        }
+    }
+}
+
+// Splits "e8=Q" (or "e8Q") into the move and the promotion piece
+fn split_promotion(san: &str) -> Result<(&str, Option<PieceType>), SanError> {
+    let (body, piece) = match san.split_once('=') {
+        Some((body, piece)) => (body, piece),
+        None if san.len() > 2 && san.ends_with(['N', 'B', 'R', 'Q']) => san.split_at(san.len() - 1),
+        None => return Ok((san, None)),
+    };
+
+    match piece {
+        "N" | "B" | "R" | "Q" => Ok((body, piece.chars().next().and_then(PieceType::from_char))),
+        _ => Err(SanError::InvalidFormat),
+    }
+}
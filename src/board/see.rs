@@ -0,0 +1,83 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::magic::AttackTable;
+use crate::types::{Color, Move, MoveFlag, PieceType, Square};
+
+// The king is worth more than everything else combined here, so a king
+// recapture onto a defended square never pays off
+const fn see_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::King => 20_000,
+        _ => piece_type.value(),
+    }
+}
+
+impl Board {
+    /// Static exchange evaluation of `mv`: the material the mover comes out
+    /// ahead by (or behind, if negative) once both sides have recaptured
+    /// on the target square for as long as it pays. Each side always
+    /// recaptures with its least valuable piece, and sliders lined up
+    /// behind a piece join in once it has gone. Promotions are valued as
+    /// the pawn they start as.
+    pub fn see(&self, mv: Move, attacks: &AttackTable) -> i32 {
+        let to = mv.to();
+        let mut occupancy = self.all_pieces;
+
+        let captured = match mv.flag() {
+            MoveFlag::EnPassant => {
+                occupancy.clear(Square::from_coords(to.file(), mv.from().rank()));
+                Some(PieceType::Pawn)
+            }
+            _ => self.piece_at(to).map(|piece| piece.piece_type),
+        };
+        let Some(mut attacker) = self.piece_at(mv.from()).map(|piece| piece.piece_type) else {
+            return 0;
+        };
+
+        // gain[d] is what the side making capture d is up if the exchange
+        // stops right after it. Every capture is made by a different piece,
+        // so one entry per square is enough for any board
+        let mut gain = [0; 64];
+        gain[0] = captured.map_or(0, see_value);
+        let mut depth = 0;
+        let mut from = Bitboard::from_square(mv.from());
+        let mut side = self.side_to_move;
+
+        while depth + 1 < gain.len() {
+            depth += 1;
+            gain[depth] = see_value(attacker) - gain[depth - 1];
+            // Whoever is to recapture is losing either way, stop early
+            if (-gain[depth - 1]).max(gain[depth]) < 0 {
+                break;
+            }
+
+            occupancy ^= from;
+            side = side.opposite();
+
+            let candidates = attacks.attackers_to(to, occupancy, self);
+            let Some((square, piece_type)) = self.least_valuable(candidates, side) else {
+                break;
+            };
+            attacker = piece_type;
+            from = Bitboard::from_square(square);
+        }
+
+        // Either side may decline to recapture, so fold back from the end
+        while depth > 1 {
+            depth -= 1;
+            gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+        }
+
+        gain[0]
+    }
+
+    fn least_valuable(&self, candidates: Bitboard, color: Color) -> Option<(Square, PieceType)> {
+        for piece_type in PieceType::ALL {
+            let pieces = candidates & self.piece_bitboards[color.index()][piece_type.index()];
+            if let Some(square) = pieces.first_square() {
+                return Some((square, piece_type));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,20 @@
+use crate::board::Board;
+use alloc::string::String;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+/// A board serializes as its FEN string, far smaller than the bitboards
+/// and readable in JSON. The move history is not part of FEN, so a
+/// deserialized board cannot see repetitions from before it was saved.
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_fen())
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fen = String::deserialize(deserializer)?;
+        Board::from_fen(&fen)
+            .map_err(|err| de::Error::custom(format_args!("invalid FEN {fen:?}: {err:?}")))
+    }
+}
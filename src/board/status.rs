@@ -0,0 +1,76 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::magic::AttackTable;
+use crate::types::{Color, PieceType};
+
+const DARK_SQUARES: Bitboard = Bitboard(0xaa55_aa55_aa55_aa55);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate,
+    Stalemate,
+    FiftyMoveDraw,
+    InsufficientMaterial,
+    ThreefoldRepetition,
+}
+
+impl Board {
+    /// Whether the game is over and why. Mate and stalemate take priority
+    /// over the draw rules, a mating move ends the game even on move 50.
+    pub fn status(&self, attacks: &AttackTable) -> GameStatus {
+        if self.generate_legal(attacks).is_empty() {
+            return if self.in_check(attacks) {
+                GameStatus::Checkmate
+            } else {
+                GameStatus::Stalemate
+            };
+        }
+
+        if self.is_threefold_repetition() {
+            GameStatus::ThreefoldRepetition
+        } else if self.is_fifty_move_draw() {
+            GameStatus::FiftyMoveDraw
+        } else if self.is_insufficient_material() {
+            GameStatus::InsufficientMaterial
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+
+    /// 50 moves by each side without a pawn move or capture.
+    pub fn is_fifty_move_draw(&self) -> bool {
+        self.halfmove_clock >= 100
+    }
+
+    /// Neither side has enough material to ever deliver mate: bare kings,
+    /// a single minor piece, or only bishops that all stand on one square
+    /// color. K+N+N vs K is not included, mate is possible there (just not
+    /// forced), so it is left to the fifty move rule.
+    pub fn is_insufficient_material(&self) -> bool {
+        let both_colors = |piece_type: PieceType| {
+            self.piece_bitboards[Color::White.index()][piece_type.index()]
+                | self.piece_bitboards[Color::Black.index()][piece_type.index()]
+        };
+
+        if (both_colors(PieceType::Pawn)
+            | both_colors(PieceType::Rook)
+            | both_colors(PieceType::Queen))
+        .is_not_empty()
+        {
+            return false;
+        }
+
+        let knights = both_colors(PieceType::Knight);
+        let bishops = both_colors(PieceType::Bishop);
+        let minors = knights.count_pieces() + bishops.count_pieces();
+
+        if minors <= 1 {
+            return true;
+        }
+
+        // Any number of bishops is harmless as long as they share a color
+        knights.is_empty()
+            && ((bishops & DARK_SQUARES).is_empty() || (bishops & !DARK_SQUARES).is_empty())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::board::castling::castling_side;
+use crate::board::{Board, FenError, Variant};
+use crate::magic::AttackTable;
+use crate::types::{CastlingSide, Color, Move, PieceType, Square, SquareError};
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MoveParseError {
+    InvalidLength,
+    InvalidSquare(SquareError),
+    InvalidPromotion,
+    NoPieceToMove,
+    OwnPieceOnTarget,
+}
+
+#[derive(Debug)]
+pub enum UciError {
+    /// Neither `startpos` nor `fen` came first.
+    MissingPosition,
+    InvalidFen(FenError),
+    /// Something other than `moves` followed the position.
+    UnexpectedToken(String),
+    InvalidMove(String, MoveParseError),
+    /// Readable, but not legal in the position reached so far.
+    IllegalMove(String),
+}
+
+impl Board {
+    /// Sets the board up from the arguments of a UCI `position` command,
+    /// `startpos [moves ...]` or `fen <fields> [moves ...]`. A leading
+    /// `position` token is skipped. On error the board is left untouched.
+    pub fn apply_uci_position(
+        &mut self,
+        tokens: &[&str],
+        attacks: &AttackTable,
+    ) -> Result<(), UciError> {
+        let tokens = match tokens.first() {
+            Some(&"position") => &tokens[1..],
+            _ => tokens,
+        };
+        let moves_at = tokens
+            .iter()
+            .position(|&token| token == "moves")
+            .unwrap_or(tokens.len());
+        let (setup, moves) = tokens.split_at(moves_at);
+
+        let mut board = match setup {
+            ["startpos"] => Board::starting_position(),
+            ["startpos", extra, ..] => return Err(UciError::UnexpectedToken(extra.to_string())),
+            ["fen", fields @ ..] => {
+                Board::from_fen(&fields.join(" ")).map_err(UciError::InvalidFen)?
+            }
+            _ => return Err(UciError::MissingPosition),
+        };
+
+        for &uci in moves.iter().skip(1) {
+            let mv = board
+                .parse_uci_move(uci)
+                .map_err(|error| UciError::InvalidMove(uci.to_string(), error))?;
+            if !board.generate_legal(attacks).contains(&mv) {
+                return Err(UciError::IllegalMove(uci.to_string()));
+            }
+            board.make_move(mv);
+        }
+
+        *self = board;
+        Ok(())
+    }
+
+    /// Reads a move in UCI long algebraic form, e.g. "e2e4", "e1g1" or
+    /// "e7e8q", working out its flag from the current position. The move
+    /// is not checked for legality beyond there being a piece of the side
+    /// to move on the origin square and not on the target. On a Chess960
+    /// board castling is the king taking its own rook, e.g. "b1a1".
+    pub fn parse_uci_move(&self, s: &str) -> Result<Move, MoveParseError> {
+        if !s.is_ascii() || !(4..=5).contains(&s.len()) {
+            return Err(MoveParseError::InvalidLength);
+        }
+
+        let from = Square::from_algebraic(&s[0..2]).map_err(MoveParseError::InvalidSquare)?;
+        let to = Square::from_algebraic(&s[2..4]).map_err(MoveParseError::InvalidSquare)?;
+        let promotion = match s[4..].chars().next() {
+            Some(ch @ ('n' | 'b' | 'r' | 'q')) => PieceType::from_char(ch),
+            Some(_) => return Err(MoveParseError::InvalidPromotion),
+            None => None,
+        };
+
+        let us = self.side_to_move;
+        let piece = match self.piece_at(from) {
+            Some(piece) if piece.color == us => piece,
+            _ => return Err(MoveParseError::NoPieceToMove),
+        };
+        if self.variant == Variant::Chess960
+            && let Some(mv) = self.chess960_castle(from, to)
+        {
+            return Ok(mv);
+        }
+
+        let capture = match self.piece_at(to) {
+            Some(target) if target.color == us => return Err(MoveParseError::OwnPieceOnTarget),
+            Some(_) => true,
+            None => false,
+        };
+
+        let promotion_rank = match us {
+            Color::White => 7,
+            Color::Black => 0,
+        };
+        let is_pawn = matches!(piece.piece_type, PieceType::Pawn);
+        // A promotion letter is required exactly when a pawn reaches the last rank
+        if promotion.is_some() != (is_pawn && to.rank() == promotion_rank) {
+            return Err(MoveParseError::InvalidPromotion);
+        }
+
+        let castle = self.variant == Variant::Standard
+            && matches!(piece.piece_type, PieceType::King)
+            && from.file() == 4
+            && from.rank() == to.rank()
+            && to.file().abs_diff(4) == 2;
+
+        let mv = match (piece.piece_type, promotion) {
+            (_, Some(piece_type)) if capture => Move::promotion_capture(from, to, piece_type),
+            (_, Some(piece_type)) => Move::promotion(from, to, piece_type),
+            _ if castle && to.file() == 6 => Move::king_castle(from, to),
+            _ if castle => Move::queen_castle(from, to),
+            (PieceType::Pawn, _) if from.rank().abs_diff(to.rank()) == 2 => {
+                Move::double_pawn_push(from, to)
+            }
+            (PieceType::Pawn, _)
+                if !capture && from.file() != to.file() && self.en_passant_square == Some(to) =>
+            {
+                Move::en_passant(from, to)
+            }
+            _ if capture => Move::capture(from, to),
+            _ => Move::quiet(from, to),
+        };
+
+        Ok(mv)
+    }
+
+    /// `mv` in UCI form. This is `Move::to_uci` except for castling on a
+    /// Chess960 board, which UCI writes as the king taking its own rook.
+    pub fn move_to_uci(&self, mv: Move) -> String {
+        if self.variant == Variant::Chess960 && mv.is_castle() {
+            let squares = self.castling_squares(self.side_to_move, castling_side(mv));
+            return format!("{}{}", squares.king_from, squares.rook_from);
+        }
+        mv.to_uci()
+    }
+
+    // The castling move a king taking its own rook stands for, if any
+    fn chess960_castle(&self, from: Square, to: Square) -> Option<Move> {
+        let us = self.side_to_move;
+        [CastlingSide::Kingside, CastlingSide::Queenside]
+            .into_iter()
+            .filter(|&side| self.castling_rights.can_castle(us, side))
+            .map(|side| (side, self.castling_squares(us, side)))
+            .find(|(_, squares)| squares.king_from == from && squares.rook_from == to)
+            .map(|(side, squares)| match side {
+                CastlingSide::Kingside => Move::king_castle(from, squares.king_to),
+                CastlingSide::Queenside => Move::queen_castle(from, squares.king_to),
+            })
+    }
+}
@@ -0,0 +1,96 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::magic::AttackTable;
+use crate::types::{CastlingSide, Color, PieceType, Square};
+
+/// The first broken invariant `Board::validate` finds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionError {
+    /// A side has no king or more than one.
+    KingCount {
+        color: Color,
+        count: u32,
+    },
+    PawnOnBackRank(Square),
+    /// The side that just moved left its own king in check.
+    OpponentInCheck,
+    /// No pawn can have just double pushed past the en passant square.
+    InvalidEnPassant(Square),
+    /// The right is set but the king or rook is not on its home square.
+    InvalidCastlingRights(Color, CastlingSide),
+}
+
+impl Board {
+    /// Checks the invariants every position reachable from the start has.
+    /// Neither `from_fen` nor `make_move` enforce these, so a malformed
+    /// FEN or a move generation bug shows up here instead of as a panic
+    /// or nonsense deeper in.
+    pub fn validate(&self, attacks: &AttackTable) -> Result<(), PositionError> {
+        for color in Color::ALL {
+            let count = self.count(color, PieceType::King);
+            if count != 1 {
+                return Err(PositionError::KingCount { color, count });
+            }
+        }
+
+        let back_ranks = Bitboard::rank(0) | Bitboard::rank(7);
+        let pawns =
+            self.pieces(Color::White, PieceType::Pawn) | self.pieces(Color::Black, PieceType::Pawn);
+        if let Some(square) = (pawns & back_ranks).first_square() {
+            return Err(PositionError::PawnOnBackRank(square));
+        }
+
+        let them = self.side_to_move.opposite();
+        if self.is_square_attacked(self.king_square(them), self.side_to_move, attacks) {
+            return Err(PositionError::OpponentInCheck);
+        }
+
+        if let Some(square) = self.en_passant_square
+            && !self.en_passant_is_consistent(square)
+        {
+            return Err(PositionError::InvalidEnPassant(square));
+        }
+
+        for color in Color::ALL {
+            for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+                if self.castling_rights.can_castle(color, side)
+                    && !self.castling_pieces_at_home(color, side)
+                {
+                    return Err(PositionError::InvalidCastlingRights(color, side));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // The square must be the one a pawn of the side that just moved
+    // skipped over: empty, with that pawn in front and its start behind
+    fn en_passant_is_consistent(&self, square: Square) -> bool {
+        let (ep_rank, pawn_rank, start_rank) = match self.side_to_move {
+            Color::White => (5, 4, 6),
+            Color::Black => (2, 3, 1),
+        };
+        if square.rank() != ep_rank {
+            return false;
+        }
+
+        let pushed = Square::from_coords(square.file(), pawn_rank);
+        let start = Square::from_coords(square.file(), start_rank);
+        let them = self.side_to_move.opposite();
+
+        !self.all_pieces.is_set(square)
+            && !self.all_pieces.is_set(start)
+            && self.pieces(them, PieceType::Pawn).is_set(pushed)
+    }
+
+    fn castling_pieces_at_home(&self, color: Color, side: CastlingSide) -> bool {
+        let squares = self.castling_squares(color, side);
+
+        self.pieces(color, PieceType::King)
+            .is_set(squares.king_from)
+            && self
+                .pieces(color, PieceType::Rook)
+                .is_set(squares.rook_from)
+    }
+}
@@ -1,6 +1,161 @@
 use crate::Piece;
-use crate::types::Square;
+use crate::board::Board;
+use crate::types::{CastlingRights, Color, Square};
+
+const SEED: u64 = 0x4c45_4d4f_4e41_5445;
+
+// splitmix64, good enough for hash keys and usable in const context
+const fn next_key(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Every random key behind the position hash. Exposed so that code
+/// outside the crate, say a transposition table or an opening book
+/// builder, can hash positions or moves itself and get the same values
+/// as [`Board::hash`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZobristKeys {
+    /// Indexed by color, piece type and square.
+    pub pieces: [[[u64; 64]; 6]; 2],
+    /// XORed in when black is to move.
+    pub side: u64,
+    /// White kingside, white queenside, black kingside, black queenside.
+    pub castling: [u64; 4],
+    /// Indexed by the file of the en passant square.
+    pub en_passant: [u64; 8],
+}
+
+impl ZobristKeys {
+    // Drawn in field order: the 768 piece keys, side, castling, ep files
+    const fn generate() -> Self {
+        let mut keys = ZobristKeys {
+            pieces: [[[0; 64]; 6]; 2],
+            side: 0,
+            castling: [0; 4],
+            en_passant: [0; 8],
+        };
+        let mut state = SEED;
+
+        let mut color = 0;
+        while color < 2 {
+            let mut piece_type = 0;
+            while piece_type < 6 {
+                let mut square = 0;
+                while square < 64 {
+                    keys.pieces[color][piece_type][square] = next_key(&mut state);
+                    square += 1;
+                }
+                piece_type += 1;
+            }
+            color += 1;
+        }
+
+        keys.side = next_key(&mut state);
+        let mut i = 0;
+        while i < 4 {
+            keys.castling[i] = next_key(&mut state);
+            i += 1;
+        }
+        let mut i = 0;
+        while i < 8 {
+            keys.en_passant[i] = next_key(&mut state);
+            i += 1;
+        }
+        keys
+    }
+
+    /// The keys every [`Board`] hashes with.
+    pub fn get() -> &'static ZobristKeys {
+        &KEYS
+    }
+
+    pub fn piece(&self, square: Square, piece: Piece) -> u64 {
+        self.pieces[piece.color.index()][piece.piece_type.index()][square.index()]
+    }
+
+    /// The combined key of every right in `rights`.
+    pub fn castling_rights(&self, rights: CastlingRights) -> u64 {
+        let flags = [
+            rights.white_kingside,
+            rights.white_queenside,
+            rights.black_kingside,
+            rights.black_queenside,
+        ];
+        let mut hash = 0;
+        for (key, set) in self.castling.iter().zip(flags) {
+            if set {
+                hash ^= key;
+            }
+        }
+        hash
+    }
+}
+
+static KEYS: ZobristKeys = ZobristKeys::generate();
 
 pub fn zobrist_piece_hash(square: Square, piece: Piece) -> u64 {
-    unimplemented!()
+    KEYS.piece(square, piece)
+}
+
+/// XORed in when black is to move.
+pub fn zobrist_side_hash() -> u64 {
+    KEYS.side
+}
+
+pub fn zobrist_castling_hash(rights: CastlingRights) -> u64 {
+    KEYS.castling_rights(rights)
+}
+
+pub fn zobrist_ep_hash(file: u8) -> u64 {
+    KEYS.en_passant[(file & 7) as usize]
+}
+
+impl Board {
+    /// The Zobrist hash of the position, updated incrementally as moves
+    /// are made and unmade.
+    ///
+    /// Positions with the same placement, side to move, castling rights
+    /// and en passant square hash equal however they were reached, the
+    /// move counters and history are not part of it. Different positions
+    /// collide only by chance, so a transposition table should still
+    /// verify the move it gets back before trusting it.
+    pub fn hash(&self) -> u64 {
+        self.position_hash
+    }
+
+    /// The key repetition detection compares positions by. It is always
+    /// equal to [`Board::hash`], so a transposition table and repetition
+    /// checks can share one key.
+    ///
+    /// It leaves out the Chess960 castling rook files, which `==` does
+    /// compare. They are fixed for a whole game, so repetitions within one
+    /// game are unaffected, but boards from different Chess960 starts can
+    /// share a key while comparing unequal.
+    pub fn repetition_key(&self) -> u64 {
+        self.position_hash
+    }
+
+    /// Hashes the position from scratch. `position_hash` is kept equal to
+    /// this incrementally, the move counters are not part of it.
+    pub fn compute_hash(&self) -> u64 {
+        let mut hash = 0;
+
+        for (square, piece) in self.pieces_iter() {
+            hash ^= zobrist_piece_hash(square, piece);
+        }
+
+        if matches!(self.side_to_move, Color::Black) {
+            hash ^= zobrist_side_hash();
+        }
+        hash ^= zobrist_castling_hash(self.castling_rights);
+        if let Some(square) = self.en_passant_square {
+            hash ^= zobrist_ep_hash(square.file());
+        }
+
+        hash
+    }
 }
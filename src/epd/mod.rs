@@ -0,0 +1,130 @@
+use crate::board::{Board, FenError, SanError};
+use crate::magic::AttackTable;
+use crate::types::Move;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[derive(Debug)]
+pub enum EpdError {
+    /// The four position fields did not parse.
+    InvalidPosition(FenError),
+    /// An operation with no opcode, or an unterminated quoted operand.
+    InvalidOperation,
+    /// A `bm` or `am` operand that is not a legal move in the position.
+    InvalidMove {
+        opcode: String,
+        san: String,
+        error: SanError,
+    },
+}
+
+/// One parsed EPD record.
+#[derive(Clone, Debug)]
+pub struct Epd {
+    pub board: Board,
+    /// Every operation's operand text by opcode, e.g. "id" -> "WAC.001".
+    /// Quotes are removed and multiple operands are joined by one space.
+    pub operations: BTreeMap<String, String>,
+    /// The `bm` operands resolved against the position.
+    pub best_moves: Vec<Move>,
+    /// The `am` operands resolved against the position.
+    pub avoid_moves: Vec<Move>,
+}
+
+/// Parses an EPD line: the first four FEN fields followed by `;`
+/// terminated operations such as `bm Qg6; id "WAC.001";`.
+pub fn parse(line: &str, attacks: &AttackTable) -> Result<Epd, EpdError> {
+    let mut rest = line.trim();
+    let mut fields = Vec::with_capacity(4);
+    for _ in 0..4 {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+
+    let board = Board::from_fen(&fields.join(" ")).map_err(EpdError::InvalidPosition)?;
+
+    let mut operations = BTreeMap::new();
+    for operation in split_operations(rest)? {
+        let (opcode, operands) = operation;
+        operations.insert(opcode.to_string(), operands.join(" "));
+    }
+
+    let resolve = |opcode: &str| -> Result<Vec<Move>, EpdError> {
+        let Some(operands) = operations.get(opcode) else {
+            return Ok(Vec::new());
+        };
+        operands
+            .split_whitespace()
+            .map(|san| {
+                board
+                    .san_to_move(san, attacks)
+                    .map_err(|error| EpdError::InvalidMove {
+                        opcode: opcode.to_string(),
+                        san: san.to_string(),
+                        error,
+                    })
+            })
+            .collect()
+    };
+    let best_moves = resolve("bm")?;
+    let avoid_moves = resolve("am")?;
+
+    Ok(Epd {
+        board,
+        operations,
+        best_moves,
+        avoid_moves,
+    })
+}
+
+// Splits `op a b; op2 "c; d";` into opcodes and operands, keeping quoted
+// operands whole even if they hold spaces or semicolons
+fn split_operations(text: &str) -> Result<Vec<(&str, Vec<&str>)>, EpdError> {
+    let mut operations = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut rest = text;
+
+    loop {
+        rest = rest.trim_start();
+        let Some(ch) = rest.chars().next() else {
+            break;
+        };
+
+        match ch {
+            ';' => {
+                operations.push(finish_operation(&mut current)?);
+                rest = &rest[1..];
+            }
+            '"' => {
+                let end = rest[1..].find('"').ok_or(EpdError::InvalidOperation)?;
+                current.push(&rest[1..end + 1]);
+                rest = &rest[end + 2..];
+            }
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == ';')
+                    .unwrap_or(rest.len());
+                current.push(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    // The final semicolon is often left off
+    if !current.is_empty() {
+        operations.push(finish_operation(&mut current)?);
+    }
+    Ok(operations)
+}
+
+fn finish_operation<'a>(current: &mut Vec<&'a str>) -> Result<(&'a str, Vec<&'a str>), EpdError> {
+    if current.is_empty() {
+        return Err(EpdError::InvalidOperation);
+    }
+    let operands = current.split_off(1);
+    Ok((current.pop().unwrap(), operands))
+}
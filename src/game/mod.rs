@@ -0,0 +1,55 @@
+use crate::board::{Board, Undo};
+use crate::types::Move;
+use alloc::vec::Vec;
+
+/// A board together with every move played on it, so moves can be taken
+/// back without keeping the `Undo` records by hand.
+#[derive(Clone, Debug)]
+pub struct Game {
+    board: Board,
+    moves: Vec<Move>,
+    // One per entry in `moves`
+    undos: Vec<Undo>,
+}
+
+impl Game {
+    /// A game starting from `board`. Repetitions are tracked by the board
+    /// itself, so they count from here, or from wherever `board` came from.
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            moves: Vec::new(),
+            undos: Vec::new(),
+        }
+    }
+
+    /// Plays `mv`, which must be legal in the current position.
+    pub fn push(&mut self, mv: Move) {
+        let undo = self.board.make_move(mv);
+        self.moves.push(mv);
+        self.undos.push(undo);
+    }
+
+    /// Takes back the last move and returns it, `None` at the start.
+    pub fn pop(&mut self) -> Option<Move> {
+        let mv = self.moves.pop()?;
+        let undo = self.undos.pop()?;
+        self.board.unmake_move(mv, undo);
+        Some(mv)
+    }
+
+    pub fn current(&self) -> &Board {
+        &self.board
+    }
+
+    /// Every move played so far, oldest first.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(Board::starting_position())
+    }
+}
@@ -1,6 +1,15 @@
+//! Without the default `std` feature the crate is `no_std` and only needs
+//! `alloc`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod bitboard;
 pub mod board;
+pub mod epd;
+pub mod game;
 pub mod magic;
+pub mod pgn;
 pub mod types;
 
 pub use bitboard::*;
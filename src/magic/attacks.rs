@@ -1,9 +1,11 @@
 use crate::Magic;
 use crate::bitboard::Bitboard;
+use crate::board::Board;
 use crate::masks::*;
 use crate::types::Square;
-use crate::types::{Color, PieceType};
+use crate::types::{Color, Piece, PieceType};
 use crate::{init_bishop_magics, init_rook_magics};
+use alloc::{boxed::Box, vec};
 
 pub struct AttackTable {
     pub rook_attacks: Box<[Bitboard]>,
@@ -14,6 +16,22 @@ pub struct AttackTable {
     pub black_pawn_attacks: [Bitboard; 64],
     pub rook_magics: [Magic; 64],
     pub bishop_magics: [Magic; 64],
+    pub line_through: Box<[Bitboard]>,
+    pub ray_between: Box<[Bitboard]>,
+    // Private so it can only be set after checking the CPU has BMI2
+    use_pext: bool,
+}
+
+/// How big the tables behind an `AttackTable` are, see `AttackTable::stats`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttackTableStats {
+    /// Rook table slots, one per blocker pattern of every square's mask.
+    pub rook_entries: usize,
+    pub bishop_entries: usize,
+    /// Slots of the largest single square's table, rook or bishop.
+    pub largest_table: usize,
+    /// Bytes the whole `AttackTable` takes up, boxed tables included.
+    pub memory_bytes: usize,
 }
 
 // Helper functions
@@ -33,21 +51,53 @@ pub fn generate_blocker_board(index: usize, mask: Bitboard) -> Bitboard {
     blockers
 }
 
+// Whether `pext` can be used on this machine. Runtime detection needs
+// std, without it only a build that already targets BMI2 uses it
+fn bmi2_available() -> bool {
+    #[cfg(all(target_arch = "x86_64", feature = "std"))]
+    {
+        std::arch::is_x86_feature_detected!("bmi2")
+    }
+    #[cfg(not(all(target_arch = "x86_64", feature = "std")))]
+    {
+        cfg!(all(target_arch = "x86_64", target_feature = "bmi2"))
+    }
+}
+
+fn table_index(magic: &Magic, blockers: Bitboard, use_pext: bool) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if use_pext {
+        // SAFETY: use_pext is only ever true when BMI2 was detected
+        return unsafe { magic.pext_index(blockers) };
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = use_pext;
+
+    magic.hash(blockers)
+}
+
 impl AttackTable {
+    /// Builds every table, indexing sliders with `pext` when the CPU has
+    /// BMI2 and with the magic multiply otherwise.
     pub fn new() -> Self {
-        println!("Initialing magic attack tables");
+        Self::with_pext(true)
+    }
+
+    /// Like `new`, but `pext` is only used if `pext` is true and the CPU
+    /// supports it. Both paths give identical attacks.
+    pub fn with_pext(pext: bool) -> Self {
+        let use_pext = pext && bmi2_available();
 
         let rook_magics = init_rook_magics();
         let bishop_magics = init_bishop_magics();
 
-        let rook_attacks = build_rook_table(&rook_magics);
-        let bishop_attacks = build_bishop_table(&bishop_magics);
-        let knight_attacks = init_knight_attacks();
-        let king_attacks = init_king_attacks();
+        let rook_attacks = build_rook_table(&rook_magics, use_pext);
+        let bishop_attacks = build_bishop_table(&bishop_magics, use_pext);
+        let knight_attacks = KNIGHT_ATTACKS;
+        let king_attacks = KING_ATTACKS;
 
         let pawn_attacks = init_pawn_attacks();
-
-        println!("Attck tables initialized");
+        let (line_through, ray_between) = init_line_tables();
 
         Self {
             rook_attacks,
@@ -58,19 +108,27 @@ impl AttackTable {
             bishop_magics,
             white_pawn_attacks: pawn_attacks.0,
             black_pawn_attacks: pawn_attacks.1,
+            line_through,
+            ray_between,
+            use_pext,
         }
     }
 
+    /// Whether slider lookups go through `pext` rather than the magic multiply.
+    pub fn uses_pext(&self) -> bool {
+        self.use_pext
+    }
+
     pub fn rook_attacks(&self, square: Square, blockers: Bitboard) -> Bitboard {
         let magic = &self.rook_magics[square.index()];
-        let hash = magic.hash(blockers);
-        self.rook_attacks[magic.offset as usize + hash]
+        let index = table_index(magic, blockers, self.use_pext);
+        self.rook_attacks[magic.offset as usize + index]
     }
 
     pub fn bishop_attacks(&self, square: Square, blockers: Bitboard) -> Bitboard {
         let magic = &self.bishop_magics[square.index()];
-        let hash = magic.hash(blockers);
-        self.bishop_attacks[magic.offset as usize + hash]
+        let index = table_index(magic, blockers, self.use_pext);
+        self.bishop_attacks[magic.offset as usize + index]
     }
 
     pub fn knight_attacks(&self, square: Square) -> Bitboard {
@@ -91,6 +149,104 @@ impl AttackTable {
             Color::Black => self.black_pawn_attacks[square.index()],
         }
     }
+
+    /// How many pieces of color `by` attack the king zone, `king_sq` and
+    /// the squares next to it. Each piece counts once however many zone
+    /// squares it hits.
+    pub fn king_zone_attacks(&self, king_sq: Square, by: Color, board: &Board) -> u32 {
+        let zone = self.king_attacks(king_sq) | Bitboard::from_square(king_sq);
+        let occupancy = board.occupancy();
+
+        let mut attackers = Bitboard::EMPTY;
+        for square in zone {
+            attackers |= self.attackers_to(square, occupancy, board);
+        }
+        (attackers & board.occupancy_of(by)).count_pieces()
+    }
+
+    /// The union of the attacks of every piece in `pieces`, with sliders
+    /// blocked by `occupancy`. An evaluator's attack map in one call.
+    /// Pawns attack diagonally forward for their own color.
+    pub fn multi_attacks(&self, pieces: &[(Square, Piece)], occupancy: Bitboard) -> Bitboard {
+        let mut union = Bitboard::EMPTY;
+        for &(square, piece) in pieces {
+            union |= match piece.piece_type {
+                PieceType::Knight => self.knight_attacks(square),
+                PieceType::Bishop => self.bishop_attacks(square, occupancy),
+                PieceType::Rook => self.rook_attacks(square, occupancy),
+                PieceType::Queen => self.queen_attacks(square, occupancy),
+                PieceType::King => self.king_attacks(square),
+                PieceType::Pawn => self.pawn_attacks(square, piece.color),
+            };
+        }
+        union
+    }
+
+    /// Every piece of either color attacking `square`, with sliders blocked
+    /// by `occupancy` rather than the board's own pieces. Pieces missing
+    /// from `occupancy` count as gone, so lifting a piece off reveals the
+    /// x-ray behind it.
+    pub fn attackers_to(&self, square: Square, occupancy: Bitboard, board: &Board) -> Bitboard {
+        let both = |piece_type| {
+            board.pieces(Color::White, piece_type) | board.pieces(Color::Black, piece_type)
+        };
+
+        let queens = both(PieceType::Queen);
+        let diagonal = both(PieceType::Bishop) | queens;
+        let orthogonal = both(PieceType::Rook) | queens;
+
+        // A pawn attacks `square` from wherever an enemy pawn on `square`
+        // would attack
+        let pawns = (self.pawn_attacks(square, Color::Black)
+            & board.pieces(Color::White, PieceType::Pawn))
+            | (self.pawn_attacks(square, Color::White)
+                & board.pieces(Color::Black, PieceType::Pawn));
+
+        (pawns
+            | (self.knight_attacks(square) & both(PieceType::Knight))
+            | (self.king_attacks(square) & both(PieceType::King))
+            | (self.bishop_attacks(square, occupancy) & diagonal)
+            | (self.rook_attacks(square, occupancy) & orthogonal))
+            & occupancy
+    }
+
+    /// The full rank, file or diagonal containing both squares, edge to edge.
+    /// Empty if the squares are not aligned (or are the same square).
+    pub fn line_through(&self, a: Square, b: Square) -> Bitboard {
+        self.line_through[a.index() * 64 + b.index()]
+    }
+
+    /// Squares strictly between `a` and `b`, see `Bitboard::between`.
+    pub fn ray_between(&self, a: Square, b: Square) -> Bitboard {
+        self.ray_between[a.index() * 64 + b.index()]
+    }
+
+    /// Entry counts and memory use, worked out from the magics' table
+    /// sizes. The pext and magic lookups share the same tables, so this
+    /// is the cost either way.
+    pub fn stats(&self) -> AttackTableStats {
+        let entries = |magics: &[Magic; 64]| magics.iter().map(Magic::table_size).sum::<usize>();
+        let rook_entries = entries(&self.rook_magics);
+        let bishop_entries = entries(&self.bishop_magics);
+        let largest_table = self
+            .rook_magics
+            .iter()
+            .chain(&self.bishop_magics)
+            .map(Magic::table_size)
+            .max()
+            .unwrap_or(0);
+
+        let boxed =
+            rook_entries + bishop_entries + self.line_through.len() + self.ray_between.len();
+        let memory_bytes = core::mem::size_of::<Self>() + boxed * core::mem::size_of::<Bitboard>();
+
+        AttackTableStats {
+            rook_entries,
+            bishop_entries,
+            largest_table,
+            memory_bytes,
+        }
+    }
 }
 
 impl Default for AttackTable {
@@ -117,57 +273,80 @@ pub fn calculate_bishop_attacks(square: Square, blockers: Bitboard) -> Bitboard
     generate_sliding_attacks(square, &BISHOP_DIRS, blockers)
 }
 
-fn build_rook_table(magics: &[Magic; 64]) -> Box<[Bitboard]> {
+fn build_rook_table(magics: &[Magic; 64], use_pext: bool) -> Box<[Bitboard]> {
     let total_size: usize = magics.iter().map(|m| m.table_size()).sum();
 
-    println!("Rook table size: {} entries", total_size);
-
     let mut table = vec![Bitboard::EMPTY; total_size].into_boxed_slice();
 
     for (sq_idx, magic) in magics.iter().enumerate().take(64) {
         let square = Square::from_index(sq_idx);
-        let mask = magic.mask;
-        let n_bits = mask.count_pieces();
 
-        for blocker_idx in 0..(1 << n_bits) {
-            let blockers = generate_blocker_board(blocker_idx, mask);
+        for blockers in magic.mask.subsets() {
             let attacks = calculate_rook_attacks(square, blockers);
 
-            let hash = magic.hash(blockers);
-            table[magic.offset as usize + hash] = attacks;
+            let index = table_index(magic, blockers, use_pext);
+            table[magic.offset as usize + index] = attacks;
         }
     }
 
     table
 }
 
-fn build_bishop_table(magics: &[Magic; 64]) -> Box<[Bitboard]> {
+fn build_bishop_table(magics: &[Magic; 64], use_pext: bool) -> Box<[Bitboard]> {
     let total_size: usize = magics.iter().map(|m| m.table_size()).sum();
 
-    println!("Bishop table size: {} entries", total_size);
-
     let mut table = vec![Bitboard::EMPTY; total_size].into_boxed_slice();
 
     for (sq_idx, magic) in magics.iter().enumerate().take(64) {
         let square = Square::from_index(sq_idx);
-        let mask = magic.mask;
-        let n_bits = mask.count_pieces();
 
-        for blocker_idx in 0..(1 << n_bits) {
-            let blockers = generate_blocker_board(blocker_idx, mask);
+        for blockers in magic.mask.subsets() {
             let attacks = calculate_bishop_attacks(square, blockers);
 
-            let hash = magic.hash(blockers);
-            table[magic.offset as usize + hash] = attacks;
+            let index = table_index(magic, blockers, use_pext);
+            table[magic.offset as usize + index] = attacks;
         }
     }
 
     table
 }
 
-fn init_knight_attacks() -> [Bitboard; 64] {
+/// Knight attacks from every square, built at compile time.
+pub const KNIGHT_ATTACKS: [Bitboard; 64] = init_knight_attacks();
+
+/// King attacks from every square, built at compile time.
+pub const KING_ATTACKS: [Bitboard; 64] = init_king_attacks();
+
+// Attacks of a piece that jumps by each (rank, file) step in `steps`,
+// written with while loops so it can run at compile time
+const fn leaper_attacks(steps: &[(i8, i8); 8]) -> [Bitboard; 64] {
     let mut attacks = [Bitboard::EMPTY; 64];
 
+    let mut sq_idx = 0;
+    while sq_idx < 64 {
+        let rank = (sq_idx / 8) as i8;
+        let file = (sq_idx % 8) as i8;
+
+        let mut bits = 0u64;
+        let mut i = 0;
+        while i < steps.len() {
+            let (dr, df) = steps[i];
+            let new_rank = rank + dr;
+            let new_file = file + df;
+
+            if new_rank >= 0 && new_rank < 8 && new_file >= 0 && new_file < 8 {
+                bits |= 1 << (new_rank * 8 + new_file);
+            }
+            i += 1;
+        }
+
+        attacks[sq_idx] = Bitboard(bits);
+        sq_idx += 1;
+    }
+    attacks
+}
+
+pub const fn init_knight_attacks() -> [Bitboard; 64] {
     const KNIGHT_MOVES: [(i8, i8); 8] = [
         (2, 1),
         (2, -1),
@@ -178,26 +357,10 @@ fn init_knight_attacks() -> [Bitboard; 64] {
         (-1, 2),
         (-1, -2),
     ];
-
-    for (sq_idx, attack) in attacks.iter_mut().enumerate() {
-        let square = Square::from_index(sq_idx);
-        let (rank, file) = (square.rank() as i8, square.file() as i8);
-
-        for &(dr, df) in &KNIGHT_MOVES {
-            let new_rank = rank + dr;
-            let new_file = file + df;
-
-            if (0..8).contains(&new_rank) && (0..8).contains(&new_file) {
-                let target = Square::from_coords(new_file as u8, new_rank as u8);
-                attack.set(target);
-            }
-        }
-    }
-    attacks
+    leaper_attacks(&KNIGHT_MOVES)
 }
 
-pub fn init_king_attacks() -> [Bitboard; 64] {
-    let mut attacks = [Bitboard::EMPTY; 64];
+pub const fn init_king_attacks() -> [Bitboard; 64] {
     const KING_MOVES: [(i8, i8); 8] = [
         (1, 0),
         (-1, 0),
@@ -208,22 +371,7 @@ pub fn init_king_attacks() -> [Bitboard; 64] {
         (-1, 1),
         (-1, -1),
     ];
-
-    for (sq_idx, attack) in attacks.iter_mut().enumerate() {
-        let square = Square::from_index(sq_idx);
-        let (rank, file) = (square.rank() as i8, square.file() as i8);
-
-        for &(dr, df) in &KING_MOVES {
-            let new_rank = rank + dr;
-            let new_file = file + df;
-
-            if (0..8).contains(&new_rank) && (0..8).contains(&new_file) {
-                let target = Square::from_coords(new_file as u8, new_rank as u8);
-                attack.set(target);
-            }
-        }
-    }
-    attacks
+    leaper_attacks(&KING_MOVES)
 }
 
 pub fn init_pawn_attacks() -> ([Bitboard; 64], [Bitboard; 64]) {
@@ -261,3 +409,32 @@ pub fn init_pawn_attacks() -> ([Bitboard; 64], [Bitboard; 64]) {
 
     (white_attacks, black_attacks)
 }
+
+pub fn init_line_tables() -> (Box<[Bitboard]>, Box<[Bitboard]>) {
+    let mut line_through = vec![Bitboard::EMPTY; 64 * 64].into_boxed_slice();
+    let mut ray_between = vec![Bitboard::EMPTY; 64 * 64].into_boxed_slice();
+
+    for a_idx in 0..64 {
+        let a = Square::from_index(a_idx);
+        let rook_a = calculate_rook_attacks(a, Bitboard::EMPTY);
+        let bishop_a = calculate_bishop_attacks(a, Bitboard::EMPTY);
+
+        for b_idx in 0..64 {
+            let b = Square::from_index(b_idx);
+            let ends = Bitboard::from_squares(&[a, b]);
+
+            let line = if rook_a.is_set(b) {
+                (rook_a & calculate_rook_attacks(b, Bitboard::EMPTY)) | ends
+            } else if bishop_a.is_set(b) {
+                (bishop_a & calculate_bishop_attacks(b, Bitboard::EMPTY)) | ends
+            } else {
+                Bitboard::EMPTY
+            };
+
+            line_through[a_idx * 64 + b_idx] = line;
+            ray_between[a_idx * 64 + b_idx] = Bitboard::between(a, b);
+        }
+    }
+
+    (line_through, ray_between)
+}
@@ -10,13 +10,12 @@ fn generate_ray(square: Square, file_delta: i8, rank_delta: i8, mask: &mut Bitbo
         cf += file_delta;
         cr += rank_delta;
 
-        // Stop if we go off the board
-        if !(0..=7).contains(&cf) || !(0..=7).contains(&cr) {
-            break;
-        }
-
-        // For occupancy masks, exclude edge squares since they can't block
-        if matches!(cf, 0 | 7) || matches!(cr, 0 | 7) {
+        // For occupancy masks, exclude the last square of each ray since
+        // a blocker there can't hide anything. Only the edge in the
+        // direction of travel counts, a rook on a1 still slides up the a-file
+        let next_f = cf + file_delta;
+        let next_r = cr + rank_delta;
+        if !(0..=7).contains(&next_f) || !(0..=7).contains(&next_r) {
             break;
         }
 
@@ -1,11 +1,14 @@
 pub mod attacks;
 pub mod masks;
+pub mod table;
 
 use crate::Bitboard;
 use crate::types::Square;
+use alloc::{vec, vec::Vec};
 
 pub use attacks::*;
 pub use masks::*;
+pub use table::*;
 
 #[derive(Clone, Copy, Default)]
 pub struct Magic {
@@ -38,9 +41,76 @@ impl Magic {
         hash as usize
     }
 
+    /// The table index BMI2 `pext` gives, the relevant blocker bits
+    /// packed down in mask order. This is the same numbering
+    /// `generate_blocker_board` uses, no magic number involved.
+    ///
+    /// # Safety
+    /// The CPU must support BMI2, see `AttackTable::uses_pext`.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "bmi2")]
+    pub unsafe fn pext_index(&self, blockers: Bitboard) -> usize {
+        core::arch::x86_64::_pext_u64(blockers.0, self.mask.0) as usize
+    }
+
     pub fn table_size(&self) -> usize {
         1 << self.mask.count_pieces()
     }
+
+    /// Whether this magic finds the right attacks for every blocker
+    /// pattern of its mask on `square`: no two patterns that need
+    /// different attacks share a slot and no slot lies past the table.
+    /// Cheap enough to check a cached or hand-edited table on load.
+    pub fn verify(&self, square: Square, is_rook: bool) -> bool {
+        let mut used = vec![None; self.table_size()];
+
+        for blockers in self.mask.subsets() {
+            let attacks = if is_rook {
+                calculate_rook_attacks(square, blockers)
+            } else {
+                calculate_bishop_attacks(square, blockers)
+            };
+
+            let Some(slot) = used.get_mut(self.hash(blockers)) else {
+                return false;
+            };
+            match *slot {
+                None => *slot = Some(attacks),
+                Some(stored) if stored == attacks => {}
+                Some(_) => return false,
+            }
+        }
+
+        true
+    }
+}
+
+// The mask itself is 64 bits of noise in a one line dump, its bit count
+// and the derived table size say more when hunting collisions
+impl core::fmt::Debug for Magic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Magic")
+            .field("mask_bits", &self.mask.count_pieces())
+            .field("magic", &format_args!("{:#018x}", self.magic))
+            .field("shift", &self.shift)
+            .field("table_size", &self.table_size())
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl core::fmt::Display for Magic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "magic {:#018x}, {} mask bits, shift {}, {} entries at offset {}",
+            self.magic,
+            self.mask.count_pieces(),
+            self.shift,
+            self.table_size(),
+            self.offset
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -50,16 +120,23 @@ struct MagicRng {
 
 impl MagicRng {
     fn new(seed: u64) -> Self {
-        Self { state: seed }
+        // xorshift never leaves an all zero state
+        let state = if seed == 0 {
+            0x9e37_79b9_7f4a_7c15
+        } else {
+            seed
+        };
+        Self { state }
     }
 
+    // xorshift64*, the low bits of a plain LCG are too regular to
+    // find magics for the larger rook masks
     fn next(&mut self) -> u64 {
-        self.state = self
-            .state
-            .wrapping_mul(6364136223846793005)
-            .wrapping_add(1442695040888963407);
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
 
-        self.state
+        self.state.wrapping_mul(2685821657736338717)
     }
 
     fn sparse(&mut self) -> u64 {
@@ -67,16 +144,18 @@ impl MagicRng {
     }
 }
 
-pub fn find_magic(square: Square, mask: Bitboard, is_rook: bool) -> u64 {
-    let n_bits = mask.count_pieces();
-    let shift = 64 - n_bits;
-    let num_patterns = 1 << n_bits;
+// Every blocker subset of `mask` alongside the attacks it produces
+fn occupancy_patterns(
+    square: Square,
+    mask: Bitboard,
+    is_rook: bool,
+) -> (Vec<Bitboard>, Vec<Bitboard>) {
+    let num_patterns = 1 << mask.count_pieces();
 
-    let mut blockers = Vec::new();
-    let mut attacks = Vec::new();
+    let mut blockers = Vec::with_capacity(num_patterns);
+    let mut attacks = Vec::with_capacity(num_patterns);
 
-    for i in 0..num_patterns {
-        let blocker_board = generate_blocker_board(i, mask);
+    for blocker_board in mask.subsets() {
         blockers.push(blocker_board);
 
         let attack_board = if is_rook {
@@ -87,85 +166,131 @@ pub fn find_magic(square: Square, mask: Bitboard, is_rook: bool) -> u64 {
         attacks.push(attack_board);
     }
 
-    let mut rng = MagicRng::new(square.index() as u64 + 12345);
-    let mut used = vec![None; num_patterns];
-
-    const MAX_ATTEMPTS: usize = 100_000_000;
-    let mut attempts = 0;
+    (blockers, attacks)
+}
 
-    'search: loop {
-        attempts += 1;
-        if attempts > MAX_ATTEMPTS {
-            panic!(
-                "Failed to find magic number for {} square {} after {} attempts. Mask has {} bits.",
-                if is_rook { "rook" } else { "bishop" },
-                square.index(),
-                MAX_ATTEMPTS,
-                n_bits
-            );
+// Whether `magic` sends every pattern to a slot that no pattern with
+// different attacks uses. `used` is scratch space, one slot per pattern.
+fn fits_patterns(
+    magic: u64,
+    mask: Bitboard,
+    blockers: &[Bitboard],
+    attacks: &[Bitboard],
+    used: &mut [Option<Bitboard>],
+) -> bool {
+    let shift = 64 - mask.count_pieces();
+    used.fill(None);
+
+    for (blocker_board, &attack_board) in blockers.iter().zip(attacks) {
+        let index = ((blocker_board.0 & mask.0).wrapping_mul(magic) >> shift) as usize;
+
+        match used[index] {
+            None => used[index] = Some(attack_board),
+            Some(stored) if stored == attack_board => {}
+            Some(_) => return false,
         }
+    }
 
-        let magic = rng.sparse();
+    true
+}
 
-        if ((mask.0.wrapping_mul(magic)) >> 56).count_ones() < 6 {
-            continue;
-        }
+#[derive(Debug, PartialEq, Eq)]
+pub enum MagicError {
+    /// No collision-free magic turned up within the attempt limit.
+    TooManyAttempts { square: Square, bits: u32 },
+}
 
-        used.fill(None);
+/// Attempt limit `find_magic` uses, far more than any square needs.
+pub const MAX_MAGIC_ATTEMPTS: usize = 100_000_000;
 
-        for i in 0..blockers.len() {
-            let index = ((blockers[i].0 & mask.0).wrapping_mul(magic) >> shift) as usize;
+/// Base seed of the default search, square `i` starts from `DEFAULT_MAGIC_SEED + i`.
+pub const DEFAULT_MAGIC_SEED: u64 = 12345;
 
-            match used[index] {
-                None => used[index] = Some(attacks[i]),
-                Some(stored) if stored == attacks[i] => continue,
-                Some(_) => continue 'search,
-            }
-        }
+pub fn find_magic(square: Square, mask: Bitboard, is_rook: bool) -> Result<u64, MagicError> {
+    find_magic_with_limit(square, mask, is_rook, MAX_MAGIC_ATTEMPTS)
+}
 
-        if attempts > 1000 {
-            println!(
-                "Found magic for {} square {} after {} attempts",
-                if is_rook { "rook" } else { "bishop" },
-                square.index(),
-                attempts
-            );
-        }
+/// `find_magic` with the RNG seeded from `seed` rather than the square.
+/// The same seed always finds the same magic.
+pub fn find_magic_with_seed(
+    square: Square,
+    mask: Bitboard,
+    is_rook: bool,
+    seed: u64,
+) -> Result<u64, MagicError> {
+    search_magic(square, mask, is_rook, seed, MAX_MAGIC_ATTEMPTS)
+}
 
-        return magic;
-    }
+/// `find_magic` giving up after `max_attempts` candidates, for search
+/// loops that would rather retry differently than wait.
+pub fn find_magic_with_limit(
+    square: Square,
+    mask: Bitboard,
+    is_rook: bool,
+    max_attempts: usize,
+) -> Result<u64, MagicError> {
+    let seed = DEFAULT_MAGIC_SEED + square.index() as u64;
+    search_magic(square, mask, is_rook, seed, max_attempts)
 }
 
-pub fn init_bishop_magics() -> [Magic; 64] {
-    let mut magics = [Magic {
-        mask: Bitboard::EMPTY,
-        magic: 0,
-        shift: 0,
-        offset: 0,
-    }; 64];
+fn search_magic(
+    square: Square,
+    mask: Bitboard,
+    is_rook: bool,
+    seed: u64,
+    max_attempts: usize,
+) -> Result<u64, MagicError> {
+    let (blockers, attacks) = occupancy_patterns(square, mask, is_rook);
 
-    let mut offset = 0;
+    let mut rng = MagicRng::new(seed);
+    let mut used = vec![None; blockers.len()];
 
-    #[allow(clippy::needless_range_loop)]
-    for sq_idx in 0..64 {
-        let square = Square::from_index(sq_idx);
-        let mask = generate_bishop_mask(square);
-        let magic_number = find_magic(square, mask, false); // false = bishop
+    for _ in 0..max_attempts {
+        let magic = rng.sparse();
 
-        magics[sq_idx] = Magic {
-            mask,
-            magic: magic_number,
-            shift: 64 - mask.count_pieces(),
-            offset,
-        };
+        if ((mask.0.wrapping_mul(magic)) >> 56).count_ones() < 6 {
+            continue;
+        }
 
-        offset += magics[sq_idx].table_size() as u32;
+        if !fits_patterns(magic, mask, &blockers, &attacks, &mut used) {
+            continue;
+        }
+
+        return Ok(magic);
     }
 
-    magics
+    Err(MagicError::TooManyAttempts {
+        square,
+        bits: mask.count_pieces(),
+    })
 }
 
-pub fn init_rook_magics() -> [Magic; 64] {
+/// Searches magics for every square and prints them as the `ROOK_MAGICS`
+/// and `BISHOP_MAGICS` tables, ready to paste over `magic/table.rs`.
+#[cfg(feature = "std")]
+pub fn find_all_magics() {
+    let rook = search_magics(DEFAULT_MAGIC_SEED, true).expect("rook magic search gave up");
+    let bishop = search_magics(DEFAULT_MAGIC_SEED, false).expect("bishop magic search gave up");
+
+    // Printed only once the search is done so the tables come out in one piece
+    for (name, magics) in [("ROOK_MAGICS", rook), ("BISHOP_MAGICS", bishop)] {
+        println!("pub const {}: [u64; 64] = [", name);
+        for magic in magics {
+            println!(
+                "    0x{:04x}_{:04x}_{:04x}_{:04x},",
+                magic >> 48,
+                (magic >> 32) & 0xffff,
+                (magic >> 16) & 0xffff,
+                magic & 0xffff
+            );
+        }
+        println!("];");
+    }
+}
+
+// One `Magic` per square, with each square's slice of the attack table
+// following on from the previous one
+fn layout_magics(numbers: &[u64; 64], is_rook: bool) -> [Magic; 64] {
     let mut magics = [Magic {
         mask: Bitboard::EMPTY,
         magic: 0,
@@ -178,10 +303,14 @@ pub fn init_rook_magics() -> [Magic; 64] {
     #[allow(clippy::needless_range_loop)]
     for sq_idx in 0..64 {
         let square = Square::from_index(sq_idx);
-        let mask = generate_rook_mask(square);
+        let mask = if is_rook {
+            generate_rook_mask(square)
+        } else {
+            generate_bishop_mask(square)
+        };
 
         if mask.count_pieces() == 0 {
-            // Corner squares have empty masks - they don't need magic numbers
+            // Empty masks don't need magic numbers
             magics[sq_idx] = Magic {
                 mask: Bitboard::EMPTY,
                 magic: 0,
@@ -191,17 +320,76 @@ pub fn init_rook_magics() -> [Magic; 64] {
             continue;
         }
 
-        let magic_number = find_magic(square, mask, true); // true = rook
-
-        magics[sq_idx] = Magic {
-            mask,
-            magic: magic_number,
-            shift: 64 - mask.count_pieces(),
-            offset,
-        };
+        magics[sq_idx] = Magic::new(mask, numbers[sq_idx], offset);
+        debug_assert!(
+            magics[sq_idx].verify(square, is_rook),
+            "bad {} magic for square {}",
+            if is_rook { "rook" } else { "bishop" },
+            sq_idx
+        );
 
         offset += magics[sq_idx].table_size() as u32;
     }
 
     magics
 }
+
+fn search_square(sq_idx: usize, seed: u64, is_rook: bool) -> Result<u64, MagicError> {
+    let square = Square::from_index(sq_idx);
+    let mask = if is_rook {
+        generate_rook_mask(square)
+    } else {
+        generate_bishop_mask(square)
+    };
+    find_magic_with_seed(square, mask, is_rook, seed.wrapping_add(sq_idx as u64))
+}
+
+// Searches every square, square `i` seeded with `seed + i`
+#[cfg(not(feature = "parallel"))]
+fn search_magics(seed: u64, is_rook: bool) -> Result<[u64; 64], MagicError> {
+    let mut numbers = [0; 64];
+
+    for (sq_idx, number) in numbers.iter_mut().enumerate() {
+        *number = search_square(sq_idx, seed, is_rook)?;
+    }
+
+    Ok(numbers)
+}
+
+// Every square's search is seeded on its own, so spreading them over
+// threads finds exactly the magics the sequential loop does
+#[cfg(feature = "parallel")]
+fn search_magics(seed: u64, is_rook: bool) -> Result<[u64; 64], MagicError> {
+    use rayon::prelude::*;
+
+    let found: Vec<u64> = (0..64)
+        .into_par_iter()
+        .map(|sq_idx| search_square(sq_idx, seed, is_rook))
+        .collect::<Result<_, _>>()?;
+
+    let mut numbers = [0; 64];
+    numbers.copy_from_slice(&found);
+    Ok(numbers)
+}
+
+/// Bishop magics from the embedded `BISHOP_MAGICS` table.
+pub fn init_bishop_magics() -> [Magic; 64] {
+    layout_magics(&BISHOP_MAGICS, false)
+}
+
+/// Bishop magics searched from scratch. `DEFAULT_MAGIC_SEED` reproduces
+/// the embedded table, any other seed gives a different valid one. With
+/// the `parallel` feature the squares are searched on rayon's pool.
+pub fn init_bishop_magics_with_seed(seed: u64) -> Result<[Magic; 64], MagicError> {
+    Ok(layout_magics(&search_magics(seed, false)?, false))
+}
+
+/// Rook magics from the embedded `ROOK_MAGICS` table.
+pub fn init_rook_magics() -> [Magic; 64] {
+    layout_magics(&ROOK_MAGICS, true)
+}
+
+/// Rook magics searched from scratch, see `init_bishop_magics_with_seed`.
+pub fn init_rook_magics_with_seed(seed: u64) -> Result<[Magic; 64], MagicError> {
+    Ok(layout_magics(&search_magics(seed, true)?, true))
+}
@@ -0,0 +1,136 @@
+// Generated by `find_all_magics`, any collision-free magics will do. Rerun it
+// and paste the output here if the masks or the search ever change.
+
+pub const ROOK_MAGICS: [u64; 64] = [
+    0x2180_0040_0020_8112,
+    0x00c0_2000_4000_1004,
+    0x4080_1000_0c80_2000,
+    0x4080_1000_0408_0081,
+    0x4600_200c_280a_0010,
+    0x0200_0200_1004_0108,
+    0x0400_0218_0920_8410,
+    0x0200_0020_4882_1401,
+    0x811c_800e_8028_4000,
+    0x2850_c020_1000_c000,
+    0x0400_8010_0020_0080,
+    0x0123_0020_0810_0100,
+    0x0000_8008_0080_0400,
+    0x0416_0002_0008_4450,
+    0x213e_0001_0802_0024,
+    0x0801_0002_0080_4100,
+    0x0040_0080_0090_4021,
+    0x0040_00a0_1000_2800,
+    0x0000_8080_1000_2000,
+    0x1020_2100_1000_0900,
+    0x2504_0080_0480_0800,
+    0x8001_0100_0204_0008,
+    0x0200_1400_2158_0210,
+    0x4800_0200_1043_8405,
+    0x0280_0042_4000_2008,
+    0x0220_4000_8020_0086,
+    0x0000_4082_0010_2200,
+    0x1002_020a_0010_2040,
+    0x2004_0024_8028_0080,
+    0x100a_0002_0004_0810,
+    0x0000_1004_0001_0802,
+    0x4010_2412_0000_4091,
+    0x0008_4000_9080_0020,
+    0x0082_4020_0240_1001,
+    0x00f0_0800_2020_0400,
+    0x0000_2100_0900_1001,
+    0x0804_8008_0080_0400,
+    0x0010_0410_0801_2040,
+    0x8010_0208_0400_1081,
+    0x1001_065c_0200_1081,
+    0x0050_2080_4002_8000,
+    0x9010_0050_2000_4000,
+    0x0000_2001_4103_0010,
+    0x2008_1001_0009_0020,
+    0x0004_0801_0005_0010,
+    0xd84a_0004_0080_8002,
+    0xa800_0108_9004_0042,
+    0x0000_0400_8042_0001,
+    0x0080_0040_0420_0440,
+    0x0020_0080_4000_2080,
+    0x06d0_00a0_0408_0220,
+    0x8006_0800_8210_0080,
+    0x4c81_8008_0124_0280,
+    0x0222_0200_0400_8080,
+    0x0800_0230_0821_0400,
+    0x0080_0040_8104_0200,
+    0x0680_a8c1_8000_1101,
+    0x0040_0010_4229_0081,
+    0x0000_0d00_2000_1041,
+    0x1810_0020_0411_0901,
+    0x0212_0050_2085_0802,
+    0x0881_0008_0604_0071,
+    0x9004_8581_3210_0834,
+    0x0000_4403_0080_416e,
+];
+
+pub const BISHOP_MAGICS: [u64; 64] = [
+    0x0049_0808_0804_2420,
+    0x0002_480a_2c82_000a,
+    0x0310_9400_8021_0004,
+    0x3116_1a02_0640_0002,
+    0x216c_0520_68e2_a000,
+    0x4008_4812_1040_8044,
+    0x0004_8211_10c0_1480,
+    0x0d02_0901_1090_0400,
+    0x0050_0620_2841_0104,
+    0x0000_9022_0821_042a,
+    0x0002_0418_0481_0880,
+    0x4298_0404_0090_210a,
+    0x0004_0848_4001_0043,
+    0x8400_0110_0210_8000,
+    0x2019_0a28_9008_08a8,
+    0x0092_8304_1a0a_2200,
+    0x0448_0020_8801_4808,
+    0x00a0_8208_0200_9202,
+    0x9508_0044_4044_0084,
+    0x0028_0213_8200_4023,
+    0x0009_0048_1140_0004,
+    0x000b_0018_0882_1002,
+    0x0031_018e_1101_a019,
+    0x0002_0400_4a00_8401,
+    0x0808_04e0_6020_2284,
+    0x2090_1004_0421_0208,
+    0x9000_4042_0404_0080,
+    0x2804_0042_0c01_0002,
+    0x1101_0100_1010_4000,
+    0x2400_8600_0088_2402,
+    0x00c4_0ca0_a042_1008,
+    0x0a00_a100_0021_0804,
+    0x0208_2090_0106_5400,
+    0x0000_9010_1014_3419,
+    0x0200_1804_0002_0400,
+    0x0652_0042_4034_0100,
+    0x0022_0084_0002_0020,
+    0x6008_3000_2041_0082,
+    0x6002_0092_0001_0814,
+    0x8000_a081_0022_0110,
+    0x0002_0a20_2000_4600,
+    0x0140_6110_0802_9000,
+    0x4000_0c04_0200_0400,
+    0x0880_0240_1044_2200,
+    0x0020_2002_0411_5080,
+    0x00c0_0808_0220_2440,
+    0x0010_0400_8088_4400,
+    0x0408_1801_1020_4040,
+    0x1105_0808_8c24_8081,
+    0xa903_0080_84e0_0008,
+    0x0080_e604_4222_0000,
+    0x0860_0000_2288_0808,
+    0x2002_0040_1044_5000,
+    0x0000_40e8_2105_0300,
+    0x0010_e001_4400_8080,
+    0x0022_0808_08a1_8005,
+    0x4108_4100_5002_2000,
+    0x4011_0100_4202_2000,
+    0x8040_8041_0084_b000,
+    0x0001_4110_0084_2400,
+    0x0100_0020_1002_0220,
+    0x2000_0040_03c4_0d00,
+    0x0008_4008_a184_0480,
+    0x7008_8208_0a0c_0110,
+];
@@ -10,10 +10,10 @@ fn main() {
     println!("\n=== Basic Attack Tests ===");
 
     let test_squares = [
-        (Square::from_coords(0, 0), "a1"),
-        (Square::from_coords(4, 3), "e4"),
-        (Square::from_coords(7, 7), "h8"),
-        (Square::from_coords(3, 3), "d4"),
+        (Square::A1, "a1"),
+        (Square::E4, "e4"),
+        (Square::H8, "h8"),
+        (Square::D4, "d4"),
     ];
 
     for (square, name) in test_squares {
@@ -31,10 +31,10 @@ fn main() {
     println!("\n=== Knight and King Pattern Tests ===");
 
     let special_squares = [
-        (Square::from_coords(1, 1), "b2 (near corner)"),
-        (Square::from_coords(4, 4), "e5 (center)"),
-        (Square::from_coords(0, 7), "a8 (corner)"),
-        (Square::from_coords(7, 0), "h1 (corner)"),
+        (Square::B2, "b2 (near corner)"),
+        (Square::E5, "e5 (center)"),
+        (Square::A8, "a8 (corner)"),
+        (Square::H1, "h1 (corner)"),
     ];
 
     for (square, name) in special_squares {
@@ -77,12 +77,12 @@ fn main() {
     println!("\n=== Pawn Attack Tests ===");
 
     let pawn_test_squares = [
-        (Square::from_coords(0, 1), "a2 (white starting)"),
-        (Square::from_coords(4, 3), "e4 (center)"),
-        (Square::from_coords(7, 6), "h7 (black starting)"),
-        (Square::from_coords(0, 0), "a1 (corner)"),
-        (Square::from_coords(7, 7), "h8 (corner)"),
-        (Square::from_coords(3, 6), "d7 (near promotion)"),
+        (Square::A2, "a2 (white starting)"),
+        (Square::E4, "e4 (center)"),
+        (Square::H7, "h7 (black starting)"),
+        (Square::A1, "a1 (corner)"),
+        (Square::H8, "h8 (corner)"),
+        (Square::D7, "d7 (near promotion)"),
     ];
 
     for (square, name) in pawn_test_squares {
@@ -0,0 +1,107 @@
+use crate::board::{Board, SanError};
+use crate::magic::AttackTable;
+use crate::types::Move;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PgnError {
+    /// A `{` comment with no closing `}`.
+    UnterminatedComment,
+    /// A `(` variation with no closing `)`, or a stray `)`.
+    UnbalancedVariation,
+    /// The move at `index` (0 = white's first) did not resolve.
+    InvalidMove {
+        index: usize,
+        san: String,
+        error: SanError,
+    },
+}
+
+/// Replays the mainline of PGN movetext from `start`, returning its moves
+/// in order. Move numbers, NAGs like `$1`, result tokens, `{}` and `;`
+/// comments are skipped. Variations in `()` are skipped too, only the
+/// mainline is followed.
+pub fn parse_movetext(
+    start: &Board,
+    movetext: &str,
+    attacks: &AttackTable,
+) -> Result<Vec<Move>, PgnError> {
+    let mut board = start.clone();
+    let mut moves = Vec::new();
+
+    for san in mainline_tokens(movetext)? {
+        let mv = board
+            .san_to_move(san, attacks)
+            .map_err(|error| PgnError::InvalidMove {
+                index: moves.len(),
+                san: san.to_string(),
+                error,
+            })?;
+        board.make_move(mv);
+        moves.push(mv);
+    }
+
+    Ok(moves)
+}
+
+// The SAN tokens of the mainline, with everything else stripped
+fn mainline_tokens(movetext: &str) -> Result<Vec<&str>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut rest = movetext;
+
+    while let Some(ch) = rest.chars().next() {
+        match ch {
+            '{' => {
+                let end = rest.find('}').ok_or(PgnError::UnterminatedComment)?;
+                rest = &rest[end + 1..];
+                continue;
+            }
+            ';' => {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                rest = &rest[end..];
+                continue;
+            }
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or(PgnError::UnbalancedVariation)?,
+            _ if ch.is_whitespace() => {}
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || "{;()".contains(c))
+                    .unwrap_or(rest.len());
+                if depth == 0
+                    && let Some(san) = strip_move_number(&rest[..end])
+                {
+                    tokens.push(san);
+                }
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    if depth != 0 {
+        return Err(PgnError::UnbalancedVariation);
+    }
+    Ok(tokens)
+}
+
+// "12." and "12..." go, as does the number glued onto "12.e4". Results
+// and NAGs are dropped entirely.
+fn strip_move_number(token: &str) -> Option<&str> {
+    if matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*") || token.starts_with('$') {
+        return None;
+    }
+
+    let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let san = match token[digits..].strip_prefix('.') {
+        Some(after) => after.trim_start_matches('.'),
+        None => token,
+    };
+
+    (!san.is_empty()).then_some(san)
+}
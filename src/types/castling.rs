@@ -1,4 +1,15 @@
-#[derive(Clone, Copy, Debug)]
+use crate::FenError;
+use crate::types::Color;
+use alloc::string::String;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastlingSide {
+    Kingside,
+    Queenside,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CastlingRights {
     pub white_kingside: bool,
     pub white_queenside: bool,
@@ -17,4 +28,105 @@ impl CastlingRights {
             black_queenside: true,
         }
     }
+
+    pub const fn none() -> Self {
+        Self {
+            white_kingside: false,
+            white_queenside: false,
+
+            black_kingside: false,
+            black_queenside: false,
+        }
+    }
+
+    pub const fn can_castle(&self, color: Color, side: CastlingSide) -> bool {
+        match (color, side) {
+            (Color::White, CastlingSide::Kingside) => self.white_kingside,
+            (Color::White, CastlingSide::Queenside) => self.white_queenside,
+            (Color::Black, CastlingSide::Kingside) => self.black_kingside,
+            (Color::Black, CastlingSide::Queenside) => self.black_queenside,
+        }
+    }
+
+    pub fn add(&mut self, color: Color, side: CastlingSide) {
+        *self.flag_mut(color, side) = true;
+    }
+
+    pub fn remove(&mut self, color: Color, side: CastlingSide) {
+        *self.flag_mut(color, side) = false;
+    }
+
+    fn flag_mut(&mut self, color: Color, side: CastlingSide) -> &mut bool {
+        match (color, side) {
+            (Color::White, CastlingSide::Kingside) => &mut self.white_kingside,
+            (Color::White, CastlingSide::Queenside) => &mut self.white_queenside,
+            (Color::Black, CastlingSide::Kingside) => &mut self.black_kingside,
+            (Color::Black, CastlingSide::Queenside) => &mut self.black_queenside,
+        }
+    }
+
+    pub fn remove_kingside(&mut self, color: Color) {
+        self.remove(color, CastlingSide::Kingside);
+    }
+
+    pub fn remove_queenside(&mut self, color: Color) {
+        self.remove(color, CastlingSide::Queenside);
+    }
+
+    /// Drops both of `color`'s rights, e.g. once its king has moved.
+    pub fn remove_color(&mut self, color: Color) {
+        self.remove_kingside(color);
+        self.remove_queenside(color);
+    }
+
+    /// Parses the castling field of a FEN, e.g. "KQkq", "Kq" or "-".
+    /// Each letter may appear at most once, in any order.
+    pub fn from_fen(field: &str) -> Result<Self, FenError> {
+        let mut rights = Self::none();
+
+        if field == "-" {
+            return Ok(rights);
+        }
+        if field.is_empty() {
+            return Err(FenError::InvalidCastlingRights);
+        }
+
+        for ch in field.chars() {
+            let flag = match ch {
+                'K' => &mut rights.white_kingside,
+                'Q' => &mut rights.white_queenside,
+                'k' => &mut rights.black_kingside,
+                'q' => &mut rights.black_queenside,
+                _ => return Err(FenError::InvalidCastlingRights),
+            };
+            if *flag {
+                return Err(FenError::InvalidCastlingRights);
+            }
+            *flag = true;
+        }
+
+        Ok(rights)
+    }
+
+    /// The FEN castling field in canonical `KQkq` order, "-" when empty.
+    pub fn to_fen(&self) -> String {
+        let mut field = String::new();
+        if self.white_kingside {
+            field.push('K');
+        }
+        if self.white_queenside {
+            field.push('Q');
+        }
+        if self.black_kingside {
+            field.push('k');
+        }
+        if self.black_queenside {
+            field.push('q');
+        }
+
+        if field.is_empty() {
+            field.push('-');
+        }
+        field
+    }
 }
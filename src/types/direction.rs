@@ -0,0 +1,75 @@
+use crate::types::Color;
+
+/// One step on the board, stored as the change in square index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Direction(i8);
+
+impl Direction {
+    pub const NORTH: Self = Self(8);
+    pub const NORTHEAST: Self = Self(9);
+    pub const EAST: Self = Self(1);
+    pub const SOUTHEAST: Self = Self(-7);
+    pub const SOUTH: Self = Self(-8);
+    pub const SOUTHWEST: Self = Self(-9);
+    pub const WEST: Self = Self(-1);
+    pub const NORTHWEST: Self = Self(7);
+
+    pub const ALL: [Direction; 8] = [
+        Self::NORTH,
+        Self::NORTHEAST,
+        Self::EAST,
+        Self::SOUTHEAST,
+        Self::SOUTH,
+        Self::SOUTHWEST,
+        Self::WEST,
+        Self::NORTHWEST,
+    ];
+
+    /// The raw change in square index. Adding this to an index does not
+    /// check for wrapping around the board edges, see `Square::offset`.
+    pub const fn value(self) -> i8 {
+        self.0
+    }
+
+    /// Returns the (file, rank) change of one step in this direction.
+    pub const fn deltas(self) -> (i8, i8) {
+        match self.0 {
+            8 => (0, 1),
+            9 => (1, 1),
+            1 => (1, 0),
+            -7 => (1, -1),
+            -8 => (0, -1),
+            -9 => (-1, -1),
+            -1 => (-1, 0),
+            7 => (-1, 1),
+            _ => unreachable!(),
+        }
+    }
+
+    /// The opposite direction, `NORTH` for `SOUTH` and so on.
+    pub const fn flip(self) -> Self {
+        Self(-self.0)
+    }
+
+    /// This direction as seen by `color`, where north is forward. Black's
+    /// forward is the board's south, so its directions are flipped.
+    pub const fn relative(self, color: Color) -> Self {
+        match color {
+            Color::White => self,
+            Color::Black => self.flip(),
+        }
+    }
+
+    /// The direction of one step changing the file by `file_delta` and the
+    /// rank by `rank_delta`, both in -1..=1. `None` for anything else,
+    /// including standing still.
+    pub const fn from_deltas(file_delta: i8, rank_delta: i8) -> Option<Self> {
+        if file_delta < -1 || file_delta > 1 || rank_delta < -1 || rank_delta > 1 {
+            return None;
+        }
+        if file_delta == 0 && rank_delta == 0 {
+            return None;
+        }
+        Some(Self(rank_delta * 8 + file_delta))
+    }
+}
@@ -1,9 +1,15 @@
 pub mod castling;
 pub mod color;
+pub mod direction;
+pub mod move_list;
+pub mod moves;
 pub mod piece;
 pub mod square;
 
 pub use castling::*;
 pub use color::*;
+pub use direction::*;
+pub use move_list::*;
+pub use moves::*;
 pub use piece::*;
 pub use square::*;
@@ -0,0 +1,123 @@
+use crate::types::{PieceType, Square};
+use alloc::string::{String, ToString};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveFlag {
+    Quiet,
+    Capture,
+    DoublePawnPush,
+    EnPassant,
+    KingCastle,
+    QueenCastle,
+    Promotion,
+    PromotionCapture,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    from: Square,
+    to: Square,
+    promotion: Option<PieceType>,
+    flag: MoveFlag,
+}
+
+impl Move {
+    pub const fn new(from: Square, to: Square, flag: MoveFlag) -> Self {
+        Self {
+            from,
+            to,
+            promotion: None,
+            flag,
+        }
+    }
+
+    pub const fn quiet(from: Square, to: Square) -> Self {
+        Self::new(from, to, MoveFlag::Quiet)
+    }
+
+    pub const fn capture(from: Square, to: Square) -> Self {
+        Self::new(from, to, MoveFlag::Capture)
+    }
+
+    pub const fn double_pawn_push(from: Square, to: Square) -> Self {
+        Self::new(from, to, MoveFlag::DoublePawnPush)
+    }
+
+    pub const fn en_passant(from: Square, to: Square) -> Self {
+        Self::new(from, to, MoveFlag::EnPassant)
+    }
+
+    /// Castling is encoded as the king's move, e.g. e1g1.
+    pub const fn king_castle(from: Square, to: Square) -> Self {
+        Self::new(from, to, MoveFlag::KingCastle)
+    }
+
+    pub const fn queen_castle(from: Square, to: Square) -> Self {
+        Self::new(from, to, MoveFlag::QueenCastle)
+    }
+
+    pub const fn promotion(from: Square, to: Square, piece: PieceType) -> Self {
+        Self {
+            from,
+            to,
+            promotion: Some(piece),
+            flag: MoveFlag::Promotion,
+        }
+    }
+
+    pub const fn promotion_capture(from: Square, to: Square, piece: PieceType) -> Self {
+        Self {
+            from,
+            to,
+            promotion: Some(piece),
+            flag: MoveFlag::PromotionCapture,
+        }
+    }
+
+    pub const fn from(self) -> Square {
+        self.from
+    }
+
+    pub const fn to(self) -> Square {
+        self.to
+    }
+
+    pub const fn promotion_piece(self) -> Option<PieceType> {
+        self.promotion
+    }
+
+    pub const fn flag(self) -> MoveFlag {
+        self.flag
+    }
+
+    pub const fn is_capture(self) -> bool {
+        matches!(
+            self.flag,
+            MoveFlag::Capture | MoveFlag::EnPassant | MoveFlag::PromotionCapture
+        )
+    }
+
+    pub const fn is_promotion(self) -> bool {
+        matches!(self.flag, MoveFlag::Promotion | MoveFlag::PromotionCapture)
+    }
+
+    pub const fn is_castle(self) -> bool {
+        matches!(self.flag, MoveFlag::KingCastle | MoveFlag::QueenCastle)
+    }
+
+    /// Long algebraic form as UCI expects it, same as `Display`.
+    pub fn to_uci(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl core::fmt::Display for Move {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}{}", self.from, self.to)?;
+
+        if let Some(piece) = self.promotion {
+            write!(f, "{}", piece.to_char())?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{FenError, types::color::Color};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PieceType {
     Pawn,
     Knight,
@@ -10,14 +11,94 @@ pub enum PieceType {
     King,
 }
 
-#[derive(Clone, Copy, Debug)]
+impl PieceType {
+    /// Every type in `piece_bitboards` order, cheapest first.
+    pub const ALL: [PieceType; 6] = [
+        PieceType::Pawn,
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Queen,
+        PieceType::King,
+    ];
+
+    pub const PAWN_VALUE: i32 = 100;
+    pub const KNIGHT_VALUE: i32 = 320;
+    pub const BISHOP_VALUE: i32 = 330;
+    pub const ROOK_VALUE: i32 = 500;
+    pub const QUEEN_VALUE: i32 = 900;
+    // Both sides always have one, it never tips the balance
+    pub const KING_VALUE: i32 = 0;
+
+    /// Position in per-type arrays, see `ALL`.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Parses a piece letter in either case, e.g. 'n' or 'N' for a knight.
+    pub const fn from_char(ch: char) -> Option<Self> {
+        match ch.to_ascii_lowercase() {
+            'p' => Some(PieceType::Pawn),
+            'n' => Some(PieceType::Knight),
+            'b' => Some(PieceType::Bishop),
+            'r' => Some(PieceType::Rook),
+            'q' => Some(PieceType::Queen),
+            'k' => Some(PieceType::King),
+            _ => None,
+        }
+    }
+
+    /// The lowercase piece letter.
+    pub const fn to_char(self) -> char {
+        match self {
+            PieceType::Pawn => 'p',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::Rook => 'r',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k',
+        }
+    }
+
+    /// Material value in centipawns.
+    pub const fn value(self) -> i32 {
+        match self {
+            PieceType::Pawn => Self::PAWN_VALUE,
+            PieceType::Knight => Self::KNIGHT_VALUE,
+            PieceType::Bishop => Self::BISHOP_VALUE,
+            PieceType::Rook => Self::ROOK_VALUE,
+            PieceType::Queen => Self::QUEEN_VALUE,
+            PieceType::King => Self::KING_VALUE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Piece {
     pub piece_type: PieceType,
     pub color: Color,
 }
 
 impl Piece {
-    fn from_fen_char(ch: char) -> Result<Self, FenError> {
-        unimplemented!()
+    pub fn from_fen_char(ch: char) -> Result<Self, FenError> {
+        let color = if ch.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+
+        let piece_type = PieceType::from_char(ch).ok_or(FenError::InvalidPiece)?;
+
+        Ok(Self { piece_type, color })
+    }
+
+    pub fn to_fen_char(self) -> char {
+        let ch = self.piece_type.to_char();
+
+        match self.color {
+            Color::White => ch.to_ascii_uppercase(),
+            Color::Black => ch,
+        }
     }
 }
@@ -1,16 +1,148 @@
+use crate::types::{Color, Direction};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Square(u8);
 
+/// The shade of a square on the board, kept apart from `Color` which is
+/// always a side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SquareColor {
+    Light,
+    Dark,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SquareError {
+    InvalidLength,
+    InvalidFile,
+    InvalidRank,
+}
+
 impl Square {
+    // Named squares, a1 = 0 through h8 = 63
+    pub const A1: Square = Square(0);
+    pub const B1: Square = Square(1);
+    pub const C1: Square = Square(2);
+    pub const D1: Square = Square(3);
+    pub const E1: Square = Square(4);
+    pub const F1: Square = Square(5);
+    pub const G1: Square = Square(6);
+    pub const H1: Square = Square(7);
+
+    pub const A2: Square = Square(8);
+    pub const B2: Square = Square(9);
+    pub const C2: Square = Square(10);
+    pub const D2: Square = Square(11);
+    pub const E2: Square = Square(12);
+    pub const F2: Square = Square(13);
+    pub const G2: Square = Square(14);
+    pub const H2: Square = Square(15);
+
+    pub const A3: Square = Square(16);
+    pub const B3: Square = Square(17);
+    pub const C3: Square = Square(18);
+    pub const D3: Square = Square(19);
+    pub const E3: Square = Square(20);
+    pub const F3: Square = Square(21);
+    pub const G3: Square = Square(22);
+    pub const H3: Square = Square(23);
+
+    pub const A4: Square = Square(24);
+    pub const B4: Square = Square(25);
+    pub const C4: Square = Square(26);
+    pub const D4: Square = Square(27);
+    pub const E4: Square = Square(28);
+    pub const F4: Square = Square(29);
+    pub const G4: Square = Square(30);
+    pub const H4: Square = Square(31);
+
+    pub const A5: Square = Square(32);
+    pub const B5: Square = Square(33);
+    pub const C5: Square = Square(34);
+    pub const D5: Square = Square(35);
+    pub const E5: Square = Square(36);
+    pub const F5: Square = Square(37);
+    pub const G5: Square = Square(38);
+    pub const H5: Square = Square(39);
+
+    pub const A6: Square = Square(40);
+    pub const B6: Square = Square(41);
+    pub const C6: Square = Square(42);
+    pub const D6: Square = Square(43);
+    pub const E6: Square = Square(44);
+    pub const F6: Square = Square(45);
+    pub const G6: Square = Square(46);
+    pub const H6: Square = Square(47);
+
+    pub const A7: Square = Square(48);
+    pub const B7: Square = Square(49);
+    pub const C7: Square = Square(50);
+    pub const D7: Square = Square(51);
+    pub const E7: Square = Square(52);
+    pub const F7: Square = Square(53);
+    pub const G7: Square = Square(54);
+    pub const H7: Square = Square(55);
+
+    pub const A8: Square = Square(56);
+    pub const B8: Square = Square(57);
+    pub const C8: Square = Square(58);
+    pub const D8: Square = Square(59);
+    pub const E8: Square = Square(60);
+    pub const F8: Square = Square(61);
+    pub const G8: Square = Square(62);
+    pub const H8: Square = Square(63);
+
     pub const fn from_coords(file: u8, rank: u8) -> Self {
         // This will wrap back if invalid inputs, EX:
         // from_coords(9,10) = from_coords(1,2) instead of panicing
+        // Use try_from_coords when the input is not known to be valid.
         Square((rank & 7) * 8 + (file & 7))
     }
 
+    /// Checked version of `from_coords`, returns `None` if either
+    /// coordinate is off the board instead of wrapping.
+    pub const fn try_from_coords(file: u8, rank: u8) -> Option<Self> {
+        if file >= 8 || rank >= 8 {
+            return None;
+        }
+        Some(Square(rank * 8 + file))
+    }
+
+    /// Builds a square from its 0..64 index (a1 = 0, h8 = 63).
+    /// Out of range indices trip a debug assertion, in release builds
+    /// they wrap to the low 6 bits like `from_coords` does.
     pub const fn from_index(index: usize) -> Self {
-        Square(index as u8)
+        debug_assert!(index < 64, "square index out of range");
+        Square((index & 63) as u8)
+    }
+
+    /// Checked version of `from_index`, returns `None` for `index >= 64`.
+    pub const fn try_from_index(index: usize) -> Option<Self> {
+        if index >= 64 {
+            return None;
+        }
+        Some(Square(index as u8))
+    }
+
+    /// Parses a square in algebraic notation, e.g. "e4".
+    pub fn from_algebraic(s: &str) -> Result<Self, SquareError> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 2 {
+            return Err(SquareError::InvalidLength);
+        }
+
+        let file = match bytes[0] {
+            b'a'..=b'h' => bytes[0] - b'a',
+            _ => return Err(SquareError::InvalidFile),
+        };
+        let rank = match bytes[1] {
+            b'1'..=b'8' => bytes[1] - b'1',
+            _ => return Err(SquareError::InvalidRank),
+        };
+
+        Ok(Self::from_coords(file, rank))
     }
+
     pub const fn file(self) -> u8 {
         self.0 & 7
     }
@@ -20,4 +152,98 @@ impl Square {
     pub const fn index(self) -> usize {
         self.0 as usize
     }
+
+    /// Chebyshev distance, the number of king moves between the squares.
+    pub const fn distance(self, other: Square) -> u8 {
+        let files = self.file().abs_diff(other.file());
+        let ranks = self.rank().abs_diff(other.rank());
+        if files > ranks { files } else { ranks }
+    }
+
+    /// Files plus ranks apart, the number of rook steps of one square.
+    pub const fn manhattan_distance(self, other: Square) -> u8 {
+        self.file().abs_diff(other.file()) + self.rank().abs_diff(other.rank())
+    }
+
+    /// The same file on the mirrored rank, a1 <-> a8.
+    pub const fn flip_vertical(self) -> Self {
+        Self(self.0 ^ 56)
+    }
+
+    /// The square a `color` pawn skipped over by double pushing to
+    /// `double_push_to`, the en passant target that push leaves behind.
+    ///
+    /// `double_push_to` must be on the fourth rank for white and the fifth
+    /// for black, the only squares a double push lands on.
+    pub const fn ep_target(double_push_to: Square, color: Color) -> Square {
+        let landing_rank = match color {
+            Color::White => 3,
+            Color::Black => 4,
+        };
+        debug_assert!(
+            double_push_to.rank() == landing_rank,
+            "ep_target of a square no double push lands on"
+        );
+        match color {
+            Color::White => Square(double_push_to.0 - 8),
+            Color::Black => Square(double_push_to.0 + 8),
+        }
+    }
+
+    /// a1 is dark, and the colors alternate along every rank and file.
+    pub const fn square_color(self) -> SquareColor {
+        if (self.file() + self.rank()) & 1 == 0 {
+            SquareColor::Dark
+        } else {
+            SquareColor::Light
+        }
+    }
+
+    /// Steps one square in `dir`, returns `None` if that would leave the board.
+    pub const fn offset(self, dir: Direction) -> Option<Self> {
+        let (df, dr) = dir.deltas();
+        let file = self.file() as i8 + df;
+        let rank = self.rank() as i8 + dr;
+
+        if file < 0 || rank < 0 {
+            return None;
+        }
+        Self::try_from_coords(file as u8, rank as u8)
+    }
+}
+
+impl core::str::FromStr for Square {
+    type Err = SquareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_algebraic(s)
+    }
+}
+
+impl core::fmt::Display for Square {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            (b'a' + self.file()) as char,
+            (b'1' + self.rank()) as char
+        )
+    }
+}
+
+/// Squares serialize by name, `"e4"`, rather than by index.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Square {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Square {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = alloc::string::String::deserialize(deserializer)?;
+        Square::from_algebraic(&name)
+            .map_err(|err| serde::de::Error::custom(format_args!("invalid square {name}: {err:?}")))
+    }
 }
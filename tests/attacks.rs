@@ -0,0 +1,416 @@
+mod common;
+
+use common::{KIWIPETE, attacks, board, sq, uci_move};
+use lemonate::{
+    AttackTable, Bitboard, Board, Color, Direction, KING_ATTACKS, KNIGHT_ATTACKS, Move, Piece,
+    PieceType, Square, find_magic, generate_rook_mask,
+};
+
+#[test]
+fn line_through_collinear_squares() {
+    let a = attacks();
+    assert_eq!(
+        a.line_through(sq("e2"), sq("e6")),
+        Bitboard(0x1010_1010_1010_1010)
+    );
+    assert_eq!(a.line_through(sq("b3"), sq("g3")), Bitboard(0xff << 16));
+    assert_eq!(
+        a.line_through(sq("c3"), sq("e5")),
+        Bitboard::from_squares(&[
+            sq("a1"),
+            sq("b2"),
+            sq("c3"),
+            sq("d4"),
+            sq("e5"),
+            sq("f6"),
+            sq("g7"),
+            sq("h8")
+        ])
+    );
+    assert_eq!(
+        a.line_through(sq("f3"), sq("g2")),
+        Bitboard::from_squares(&[
+            sq("d5"),
+            sq("e4"),
+            sq("f3"),
+            sq("g2"),
+            sq("h1"),
+            sq("c6"),
+            sq("b7"),
+            sq("a8")
+        ])
+    );
+    assert_eq!(
+        a.ray_between(sq("e2"), sq("e6")),
+        Bitboard::from_squares(&[sq("e3"), sq("e4"), sq("e5")])
+    );
+}
+
+#[test]
+fn line_through_non_collinear_squares_is_empty() {
+    let a = attacks();
+    assert_eq!(a.line_through(sq("e2"), sq("f4")), Bitboard::EMPTY);
+    assert_eq!(a.line_through(sq("a1"), sq("b3")), Bitboard::EMPTY);
+    assert_eq!(a.line_through(sq("d4"), sq("d4")), Bitboard::EMPTY);
+    assert_eq!(a.ray_between(sq("e2"), sq("f4")), Bitboard::EMPTY);
+}
+
+#[test]
+fn line_tables_agree_with_between_for_every_pair() {
+    let a = attacks();
+    for i in 0..64 {
+        for j in 0..64 {
+            let (x, y) = (Square::from_index(i), Square::from_index(j));
+            assert_eq!(a.ray_between(x, y), Bitboard::between(x, y));
+            let line = a.line_through(x, y);
+            assert_eq!(line, a.line_through(y, x));
+            if line.is_not_empty() {
+                assert!(line.is_set(x) && line.is_set(y));
+                assert_eq!(line & a.ray_between(x, y), a.ray_between(x, y));
+            }
+        }
+    }
+}
+
+#[test]
+fn square_attacked_by_each_piece_type() {
+    let b = board("4k3/8/2n5/8/4P3/1b6/8/R3K2q w - - 0 1");
+    let white = |name| b.is_square_attacked(sq(name), Color::White, attacks());
+    let black = |name| b.is_square_attacked(sq(name), Color::Black, attacks());
+
+    // Pawn, king and rook
+    assert!(white("d5") && white("f5"));
+    assert!(!white("e5"));
+    assert!(white("d2") && white("f2"));
+    assert!(white("a8") && white("d1"));
+    // Knight, bishop and queen
+    assert!(black("b4") && black("e5") && black("d4"));
+    assert!(black("c2") && black("a4"));
+    assert!(black("h8") && black("f1"));
+    // Both sliders stop at the first blocker
+    assert!(black("e1") && !black("c1"));
+    assert!(!white("g1"));
+    // Safe squares
+    assert!(!white("h4") && !black("g3"));
+}
+
+#[test]
+fn square_behind_the_king_is_shielded_by_it() {
+    let b = board("4r1k1/8/8/8/4K3/8/8/8 w - - 0 1");
+    let a = attacks();
+
+    // The king itself blocks the rook from e3 for is_square_attacked
+    assert!(b.is_square_attacked(sq("e4"), Color::Black, a));
+    assert!(!b.is_square_attacked(sq("e3"), Color::Black, a));
+
+    // but it can't step back along the line
+    let legal = b.generate_legal(a);
+    assert!(legal.iter().all(|mv| mv.to().file() != 4));
+    assert!(legal.iter().any(|mv| mv.to() == sq("d3")));
+}
+
+#[test]
+fn discovered_line_opens_when_the_blocker_moves() {
+    let mut b = board("4k3/8/8/8/8/8/4N3/4R2K w - - 0 1");
+    let a = attacks();
+    assert!(!b.is_square_attacked(sq("e8"), Color::White, a));
+
+    b.make_move(Move::quiet(sq("e2"), sq("c3")));
+    assert!(b.is_square_attacked(sq("e8"), Color::White, a));
+    assert!(b.in_check(a));
+}
+
+#[test]
+fn checkers_single_double_and_none() {
+    let a = attacks();
+
+    let single = board("4k3/8/8/8/8/8/8/4R1K1 b - - 0 1");
+    assert!(single.in_check(a));
+    assert_eq!(single.checkers(a), Bitboard::from_square(sq("e1")));
+
+    let double = board("4k3/8/5N2/8/8/8/8/4R1K1 b - - 0 1");
+    assert!(double.in_check(a));
+    assert_eq!(
+        double.checkers(a),
+        Bitboard::from_squares(&[sq("e1"), sq("f6")])
+    );
+    // Only king moves get out of a double check
+    assert!(
+        double
+            .generate_legal(a)
+            .iter()
+            .all(|mv| mv.from() == sq("e8"))
+    );
+
+    let quiet = board("4k3/8/8/8/8/8/4p3/4R1K1 b - - 0 1");
+    assert!(!quiet.in_check(a));
+    assert_eq!(quiet.checkers(a), Bitboard::EMPTY);
+}
+
+#[test]
+fn pinned_pieces_orthogonal_and_diagonal() {
+    let a = attacks();
+    // e3 is pinned by the rook, f2 by the h4 bishop. c3 and d2 shield
+    // each other from the a5 bishop, so neither is pinned
+    let b = board("4r2k/8/8/b7/7b/2N1N3/3P1P2/4K3 w - - 0 1");
+    assert_eq!(
+        b.pinned_pieces(a),
+        Bitboard::from_squares(&[sq("e3"), sq("f2")])
+    );
+
+    assert_eq!(b.pin_ray(sq("e3"), a), Bitboard(0x1010_1010_1010_1010));
+    assert!(b.pin_ray(sq("f2"), a).is_set(sq("h4")));
+    assert_eq!(b.pin_ray(sq("c3"), a), Bitboard::FULL);
+
+    // The pinned knight has no legal moves, the pinned pawn can't push
+    let legal = b.generate_legal(a);
+    assert!(legal.iter().all(|mv| mv.from() != sq("e3")));
+    assert!(legal.iter().all(|mv| mv.from() != sq("f2")));
+}
+
+#[test]
+fn enemy_blockers_do_not_pin() {
+    let b = board("4r2k/8/8/8/4p3/4N3/8/4K3 w - - 0 1");
+    assert_eq!(b.pinned_pieces(attacks()), Bitboard::EMPTY);
+}
+
+#[test]
+fn attackers_to_a_contested_square() {
+    // e5 is hit by pawns, knights, a bishop, a rook and a queen from both
+    // sides, the h8 bishop only through the g7 queen
+    let b = board("6kb/6q1/2np4/4p3/5P2/5N2/1B6/4R1K1 w - - 0 1");
+    let attackers = attacks().attackers_to(sq("e5"), b.occupancy(), &b);
+
+    let expected = ["f4", "f3", "b2", "e1", "d6", "c6", "g7"];
+    assert_eq!(
+        attackers,
+        Bitboard::from_squares(&expected.map(sq)),
+        "\n{attackers}"
+    );
+
+    // Lifting the queen reveals the bishop behind it
+    let lifted = b.occupancy() & !Bitboard::from_square(sq("g7"));
+    let attackers = attacks().attackers_to(sq("e5"), lifted, &b);
+    assert!(attackers.is_set(sq("h8")));
+    assert!(!attackers.is_set(sq("g7")));
+}
+
+fn white(piece_type: PieceType) -> Piece {
+    Piece {
+        piece_type,
+        color: Color::White,
+    }
+}
+
+#[test]
+fn multi_attacks_is_the_union_of_single_lookups() {
+    let b = board(KIWIPETE);
+    let occupancy = b.occupancy();
+
+    let queens = [
+        (sq("d4"), white(PieceType::Queen)),
+        (sq("f3"), white(PieceType::Queen)),
+    ];
+    assert_eq!(
+        attacks().multi_attacks(&queens, occupancy),
+        attacks().queen_attacks(sq("d4"), occupancy) | attacks().queen_attacks(sq("f3"), occupancy)
+    );
+
+    let mixed = [
+        (sq("e5"), white(PieceType::Knight)),
+        (sq("d2"), white(PieceType::Bishop)),
+        (sq("a1"), white(PieceType::Rook)),
+        (sq("e1"), white(PieceType::King)),
+    ];
+    assert_eq!(
+        attacks().multi_attacks(&mixed, occupancy),
+        attacks().knight_attacks(sq("e5"))
+            | attacks().bishop_attacks(sq("d2"), occupancy)
+            | attacks().rook_attacks(sq("a1"), occupancy)
+            | attacks().king_attacks(sq("e1"))
+    );
+    assert_eq!(attacks().multi_attacks(&[], occupancy), Bitboard::EMPTY);
+}
+
+#[test]
+fn multi_attacks_takes_pawn_direction_from_color() {
+    let black_pawn = Piece {
+        piece_type: PieceType::Pawn,
+        color: Color::Black,
+    };
+    let pawns = [(sq("d4"), white(PieceType::Pawn)), (sq("d4"), black_pawn)];
+
+    let union = attacks().multi_attacks(&pawns, Bitboard::EMPTY);
+    assert_eq!(
+        union,
+        attacks().pawn_attacks(sq("d4"), Color::White)
+            | attacks().pawn_attacks(sq("d4"), Color::Black)
+    );
+    for name in ["c5", "e5", "c3", "e3"] {
+        assert!(union.is_set(sq(name)), "{name}");
+    }
+    assert_eq!(union.count_pieces(), 4);
+}
+
+// Run by `building_tables_prints_nothing` in a child process, so whatever
+// the constructor writes reaches a real stdout instead of the test capture
+#[test]
+fn build_tables_between_markers() {
+    if std::env::var_os("LEMONATE_PRINT_CHILD").is_none() {
+        return;
+    }
+    println!("<<<");
+    AttackTable::new();
+    find_magic(sq("a1"), generate_rook_mask(sq("a1")), true).unwrap();
+    println!(">>>");
+}
+
+#[test]
+fn building_tables_prints_nothing() {
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["build_tables_between_markers", "--exact", "--nocapture"])
+        .env("LEMONATE_PRINT_CHILD", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let start = stdout.find("<<<\n").expect("child did not run") + 4;
+    let end = stdout.find(">>>").unwrap();
+    assert_eq!(&stdout[start..end], "");
+}
+
+#[test]
+fn king_zone_attacks_counts_attackers_near_the_king() {
+    // Castled behind unmoved pawns: only the c5 bishop eyes f2, while the
+    // g5 knight and c4 bishop both hit f7
+    let castled = board("r1bq1rk1/pppp1ppp/2n2n2/2b1p1N1/2B1P3/8/PPPP1PPP/RNBQ1RK1 b - - 0 1");
+    let king = castled.king_square(Color::White);
+    assert_eq!(attacks().king_zone_attacks(king, Color::Black, &castled), 1);
+    let black_king = castled.king_square(Color::Black);
+    assert_eq!(
+        attacks().king_zone_attacks(black_king, Color::White, &castled),
+        2
+    );
+
+    // In the middle of an open board every black piece reaches the zone
+    let exposed = board("3rr1k1/8/8/2q5/4K3/5n2/8/1b6 w - - 0 1");
+    let king = exposed.king_square(Color::White);
+    assert_eq!(attacks().king_zone_attacks(king, Color::Black, &exposed), 5);
+}
+
+#[test]
+fn const_leaper_tables_match_runtime_generation() {
+    const KNIGHT_STEPS: [(i8, i8); 8] = [
+        (1, 2),
+        (2, 1),
+        (2, -1),
+        (1, -2),
+        (-1, -2),
+        (-2, -1),
+        (-2, 1),
+        (-1, 2),
+    ];
+
+    for index in 0..64 {
+        let square = Square::from_index(index);
+        let from = Bitboard::from_square(square);
+
+        let mut knight = Bitboard::EMPTY;
+        for (df, dr) in KNIGHT_STEPS {
+            let file = square.file() as i8 + df;
+            let rank = square.rank() as i8 + dr;
+            if (0..8).contains(&file) && (0..8).contains(&rank) {
+                knight.set(Square::from_coords(file as u8, rank as u8));
+            }
+        }
+        let king = Direction::ALL
+            .iter()
+            .fold(Bitboard::EMPTY, |all, &dir| all | from.shift(dir));
+
+        assert_eq!(KNIGHT_ATTACKS[index], knight, "knight on {square}");
+        assert_eq!(KING_ATTACKS[index], king, "king on {square}");
+        assert_eq!(attacks().knight_attacks(square), knight);
+        assert_eq!(attacks().king_attacks(square), king);
+    }
+}
+
+#[test]
+fn attacks_by_covers_the_whole_side() {
+    let start = Board::starting_position();
+    let first_rank = Bitboard::RANKS[0] & !Bitboard::from_squares(&[sq("a1"), sq("h1")]);
+    assert_eq!(
+        start.attacks_by(Color::White, attacks()),
+        Bitboard::RANKS[2] | Bitboard::RANKS[1] | first_rank
+    );
+    assert_eq!(
+        start.attacks_by(Color::Black, attacks()),
+        start
+            .flip()
+            .attacks_by(Color::White, attacks())
+            .flip_vertical()
+    );
+
+    // The rook sees through the checked king, e6 is no escape
+    let check = board("8/8/8/4k3/8/8/8/4RK2 b - - 0 1");
+    let attacked = check.attacks_by(Color::White, attacks());
+    assert!(attacked.is_set(sq("e6")));
+    assert!(attacked.is_set(sq("e8")));
+    assert!(!attacked.is_set(sq("d6")));
+
+    // Every square it marks is attacked by the per-square test and back
+    let b = board(KIWIPETE);
+    for color in [Color::White, Color::Black] {
+        let map = b.attacks_by(color, attacks());
+        for index in 0..64 {
+            let square = Square::from_index(index);
+            assert_eq!(
+                map.is_set(square),
+                b.is_square_attacked(square, color, attacks()),
+                "{color:?} on {square}"
+            );
+        }
+    }
+}
+
+#[test]
+fn gives_check_without_making_the_move() {
+    let gives_check = |fen: &str, uci: &str| {
+        let b = board(fen);
+        b.gives_check(uci_move(&b, uci), attacks())
+    };
+
+    // Direct
+    assert!(gives_check("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a8"));
+    assert!(!gives_check("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a7"));
+    // The knight steps off the e-file and uncovers the rook
+    assert!(gives_check("4k3/8/8/8/4N3/8/8/4RK2 w - - 0 1", "e4c3"));
+    // Promoting to a queen checks along the rank, to a knight it does not
+    assert!(gives_check("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8q"));
+    assert!(!gives_check("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8n"));
+    // The castled rook lands on f1 facing the king
+    assert!(gives_check("5k2/8/8/8/8/8/8/4K2R w K - 0 1", "e1g1"));
+    // Taking en passant clears both pawns off the fifth rank
+    assert!(gives_check("8/8/8/k2pP2R/8/8/8/4K3 w - d6 0 1", "e5d6"));
+}
+
+#[test]
+fn gives_check_agrees_with_making_the_move() {
+    for fen in [
+        KIWIPETE,
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ] {
+        let b = board(fen);
+        for mv in b.generate_legal(attacks()) {
+            let mut after = b.clone();
+            after.make_move(mv);
+            assert_eq!(
+                b.gives_check(mv, attacks()),
+                after.in_check(attacks()),
+                "{fen} {mv}"
+            );
+        }
+    }
+}
@@ -0,0 +1,449 @@
+mod common;
+
+use common::{KIWIPETE, attacks, board, sq};
+use lemonate::{Bitboard, Color, Direction, PieceType, Square};
+
+fn squares(names: &[&str]) -> Bitboard {
+    let squares: Vec<Square> = names.iter().map(|&name| sq(name)).collect();
+    Bitboard::from_squares(&squares)
+}
+
+#[test]
+fn shift_moves_every_bit_one_step() {
+    let d4 = squares(&["d4"]);
+    assert_eq!(d4.shift(Direction::NORTH), squares(&["d5"]));
+    assert_eq!(d4.shift(Direction::NORTHEAST), squares(&["e5"]));
+    assert_eq!(d4.shift(Direction::EAST), squares(&["e4"]));
+    assert_eq!(d4.shift(Direction::SOUTHEAST), squares(&["e3"]));
+    assert_eq!(d4.shift(Direction::SOUTH), squares(&["d3"]));
+    assert_eq!(d4.shift(Direction::SOUTHWEST), squares(&["c3"]));
+    assert_eq!(d4.shift(Direction::WEST), squares(&["c4"]));
+    assert_eq!(d4.shift(Direction::NORTHWEST), squares(&["c5"]));
+}
+
+#[test]
+fn shift_does_not_wrap_around_the_edges() {
+    let a_file = Bitboard(0x0101_0101_0101_0101);
+    let h_file = Bitboard(0x8080_8080_8080_8080);
+    for dir in [Direction::EAST, Direction::NORTHEAST, Direction::SOUTHEAST] {
+        assert_eq!(h_file.shift(dir), Bitboard::EMPTY, "{dir:?}");
+        assert!((Bitboard::FULL.shift(dir) & a_file).is_empty(), "{dir:?}");
+    }
+    for dir in [Direction::WEST, Direction::NORTHWEST, Direction::SOUTHWEST] {
+        assert_eq!(a_file.shift(dir), Bitboard::EMPTY, "{dir:?}");
+        assert!((Bitboard::FULL.shift(dir) & h_file).is_empty(), "{dir:?}");
+    }
+    assert_eq!(
+        Bitboard(0xff00_0000_0000_0000).shift(Direction::NORTH),
+        Bitboard::EMPTY
+    );
+    assert_eq!(Bitboard(0xff).shift(Direction::SOUTH), Bitboard::EMPTY);
+}
+
+#[test]
+fn shift_matches_square_offset() {
+    // Corners, edges and the middle in one pattern
+    let pattern = squares(&["a1", "h1", "a8", "h8", "e1", "a5", "h4", "e8", "d4", "f6"]);
+    for dir in Direction::ALL {
+        let expected = pattern
+            .iter()
+            .filter_map(|square: Square| square.offset(dir))
+            .map(Bitboard::from_square)
+            .fold(Bitboard::EMPTY, |a, b| a | b);
+        assert_eq!(pattern.shift(dir), expected, "{dir:?}");
+    }
+}
+
+#[test]
+fn between_aligned_squares() {
+    assert_eq!(
+        Bitboard::between(sq("a1"), sq("a4")),
+        squares(&["a2", "a3"])
+    );
+    assert_eq!(
+        Bitboard::between(sq("h5"), sq("d5")),
+        squares(&["e5", "f5", "g5"])
+    );
+    assert_eq!(
+        Bitboard::between(sq("a1"), sq("h8")),
+        squares(&["b2", "c3", "d4", "e5", "f6", "g7"])
+    );
+    assert_eq!(
+        Bitboard::between(sq("g2"), sq("d5")),
+        squares(&["f3", "e4"])
+    );
+    // Order of the arguments doesn't matter
+    assert_eq!(
+        Bitboard::between(sq("d5"), sq("g2")),
+        Bitboard::between(sq("g2"), sq("d5"))
+    );
+}
+
+#[test]
+fn between_adjacent_and_unaligned_squares_is_empty() {
+    assert_eq!(Bitboard::between(sq("e4"), sq("e5")), Bitboard::EMPTY);
+    assert_eq!(Bitboard::between(sq("e4"), sq("f5")), Bitboard::EMPTY);
+    assert_eq!(Bitboard::between(sq("e4"), sq("e4")), Bitboard::EMPTY);
+    assert_eq!(Bitboard::between(sq("b1"), sq("c3")), Bitboard::EMPTY);
+    assert_eq!(Bitboard::between(sq("a1"), sq("h7")), Bitboard::EMPTY);
+}
+
+#[test]
+fn iter_matches_pop_lsb_and_leaves_the_board_alone() {
+    let board = Bitboard(0x8100_0000_0024_8001);
+    let iterated: Vec<Square> = board.iter().collect();
+
+    let mut popped = Vec::new();
+    let mut copy = board;
+    while let Some(square) = copy.pop_lsb() {
+        popped.push(square);
+    }
+
+    assert_eq!(iterated, popped);
+    assert_eq!(board, Bitboard(0x8100_0000_0024_8001));
+    assert_eq!(board.iter().len(), 6);
+    assert_eq!(Bitboard::EMPTY.iter().next(), None);
+}
+
+#[test]
+fn first_and_last_square() {
+    assert_eq!(Bitboard(1).first_square(), Some(sq("a1")));
+    assert_eq!(Bitboard(1).last_square(), Some(sq("a1")));
+    assert_eq!(Bitboard(1 << 63).first_square(), Some(sq("h8")));
+    assert_eq!(Bitboard(1 << 63).last_square(), Some(sq("h8")));
+
+    let mixed = squares(&["c2", "e4", "g7"]);
+    assert_eq!(mixed.first_square(), Some(sq("c2")));
+    assert_eq!(mixed.last_square(), Some(sq("g7")));
+    assert_eq!(Bitboard::FULL.first_square(), Some(sq("a1")));
+
+    assert_eq!(Bitboard::EMPTY.first_square(), None);
+    assert_eq!(Bitboard::EMPTY.last_square(), None);
+}
+
+#[test]
+fn from_squares_matches_set() {
+    let corners = Bitboard::from_squares(&[sq("a1"), sq("h8")]);
+    assert_eq!(corners.count_pieces(), 2);
+
+    let mut manual = Bitboard::EMPTY;
+    manual.set(sq("a1"));
+    manual.set(sq("h8"));
+    assert_eq!(corners, manual);
+
+    assert_eq!(Bitboard::from_square(sq("e4")), Bitboard(1 << 28));
+    assert_eq!(Bitboard::from_squares(&[]), Bitboard::EMPTY);
+    // Duplicates are harmless
+    assert_eq!(
+        Bitboard::from_squares(&[sq("e4"), sq("e4")]).count_pieces(),
+        1
+    );
+}
+
+#[test]
+fn file_and_rank_masks() {
+    let a_file = Bitboard::file(0);
+    assert_eq!(a_file.count_pieces(), 8);
+    assert!(a_file.iter().all(|square| square.file() == 0));
+
+    let eighth = Bitboard::rank(7);
+    assert_eq!(eighth.count_pieces(), 8);
+    assert!(eighth.iter().all(|square| square.rank() == 7));
+
+    for i in 0..8 {
+        assert_eq!(Bitboard::file(i), Bitboard::FILES[i as usize]);
+        assert_eq!(Bitboard::rank(i), Bitboard::RANKS[i as usize]);
+    }
+    let all_files = Bitboard::FILES.iter().fold(Bitboard::EMPTY, |a, &b| a | b);
+    assert_eq!(all_files, Bitboard::FULL);
+}
+
+#[test]
+fn flips_move_single_bits() {
+    let a1 = squares(&["a1"]);
+    assert_eq!(a1.flip_vertical(), squares(&["a8"]));
+    assert_eq!(a1.flip_horizontal(), squares(&["h1"]));
+    assert_eq!(a1.flip_diagonal(), a1);
+    assert_eq!(squares(&["h1"]).flip_diagonal(), squares(&["a8"]));
+    assert_eq!(squares(&["c2"]).flip_vertical(), squares(&["c7"]));
+    assert_eq!(squares(&["c2"]).flip_horizontal(), squares(&["f2"]));
+    assert_eq!(squares(&["c2"]).flip_diagonal(), squares(&["b3"]));
+}
+
+#[test]
+fn flipping_twice_is_the_identity() {
+    for bits in [0, 1, 0x8100_0000_0024_8001, 0x0123_4567_89ab_cdef, u64::MAX] {
+        let board = Bitboard(bits);
+        assert_eq!(board.flip_vertical().flip_vertical(), board);
+        assert_eq!(board.flip_horizontal().flip_horizontal(), board);
+        assert_eq!(board.flip_diagonal().flip_diagonal(), board);
+    }
+}
+
+#[test]
+fn north_and_south_fill() {
+    let a2 = squares(&["a2"]);
+    assert_eq!(
+        a2.north_fill(),
+        squares(&["a2", "a3", "a4", "a5", "a6", "a7", "a8"])
+    );
+    assert_eq!(a2.south_fill(), squares(&["a1", "a2"]));
+    assert_eq!(a2.file_fill(), Bitboard::file(0));
+
+    let two_files = squares(&["c4", "f7"]);
+    assert_eq!(two_files.file_fill(), Bitboard::file(2) | Bitboard::file(5));
+}
+
+#[test]
+fn squares_round_trip_in_ascending_order() {
+    for board in [
+        Bitboard::EMPTY,
+        squares(&["h8", "a1", "e4"]),
+        Bitboard::rank(1),
+        Bitboard(0x0123_4567_89ab_cdef),
+        Bitboard::FULL,
+    ] {
+        let list = board.squares();
+        assert_eq!(Bitboard::from_squares(&list), board);
+        assert_eq!(list.len() as u32, board.count_pieces());
+        assert!(
+            list.windows(2)
+                .all(|pair| pair[0].index() < pair[1].index())
+        );
+    }
+    assert_eq!(
+        squares(&["h8", "a1", "e4"]).squares(),
+        vec![sq("a1"), sq("e4"), sq("h8")]
+    );
+}
+
+#[test]
+fn pawn_pushes_from_the_start_position() {
+    let start = lemonate::Board::starting_position();
+    let empty = !start.occupancy();
+
+    let white = start.pieces(Color::White, PieceType::Pawn);
+    assert_eq!(
+        Bitboard::pawn_single_pushes(white, empty, Color::White),
+        Bitboard::RANKS[2]
+    );
+    assert_eq!(
+        Bitboard::pawn_double_pushes(white, empty, Color::White),
+        Bitboard::RANKS[3]
+    );
+
+    let black = start.pieces(Color::Black, PieceType::Pawn);
+    assert_eq!(
+        Bitboard::pawn_single_pushes(black, empty, Color::Black),
+        Bitboard::RANKS[5]
+    );
+    assert_eq!(
+        Bitboard::pawn_double_pushes(black, empty, Color::Black),
+        Bitboard::RANKS[4]
+    );
+}
+
+#[test]
+fn pawn_pushes_are_blocked_by_occupied_squares() {
+    // c3 blocks the c-pawn outright, e4 only its double push, g3 is past
+    // its starting rank
+    let b = board("4k3/8/8/8/4n3/2n3P1/2P1P3/4K3 w - - 0 1");
+    let pawns = b.pieces(Color::White, PieceType::Pawn);
+    let empty = !b.occupancy();
+
+    assert_eq!(
+        Bitboard::pawn_single_pushes(pawns, empty, Color::White),
+        squares(&["e3", "g4"])
+    );
+    assert_eq!(
+        Bitboard::pawn_double_pushes(pawns, empty, Color::White),
+        Bitboard::EMPTY
+    );
+    let without_knight = empty | squares(&["e4"]);
+    assert_eq!(
+        Bitboard::pawn_double_pushes(pawns, without_knight, Color::White),
+        squares(&["e4"])
+    );
+}
+
+#[test]
+fn shift_by_steps_without_wrapping() {
+    let rank = Bitboard::RANKS[3];
+    let east = rank.shift_by(Direction::EAST, 3);
+    assert_eq!(
+        east,
+        rank & !(Bitboard::FILES[0] | Bitboard::FILES[1] | Bitboard::FILES[2])
+    );
+    assert_eq!(rank.shift_by(Direction::WEST, 7), squares(&["a4"]));
+    assert_eq!(rank.shift_by(Direction::EAST, 8), Bitboard::EMPTY);
+
+    let d4 = squares(&["d4"]);
+    assert_eq!(d4.shift_by(Direction::NORTHEAST, 2), squares(&["f6"]));
+    assert_eq!(d4.shift_by(Direction::NORTH, 0), d4);
+    assert_eq!(d4.shift_by(Direction::NORTH, 4), squares(&["d8"]));
+    assert_eq!(d4.shift_by(Direction::NORTH, 5), Bitboard::EMPTY);
+    assert_eq!(
+        Bitboard::RANKS[0].shift_by(Direction::SOUTH, 1),
+        Bitboard::EMPTY
+    );
+}
+
+#[test]
+fn knight_attacks_all_matches_the_table() {
+    let b = board(KIWIPETE);
+    for color in [Color::White, Color::Black] {
+        let knights = b.pieces(color, PieceType::Knight);
+        assert_eq!(knights.count_pieces(), 2);
+        let expected = knights.iter().fold(Bitboard::EMPTY, |all, square| {
+            all | attacks().knight_attacks(square)
+        });
+        assert_eq!(Bitboard::knight_attacks_all(knights), expected);
+    }
+
+    // Corners and edges, where the shifts would wrap
+    for index in 0..64 {
+        let square = Square::from_index(index);
+        assert_eq!(
+            Bitboard::knight_attacks_all(Bitboard::from_square(square)),
+            attacks().knight_attacks(square)
+        );
+    }
+    assert_eq!(
+        Bitboard::knight_attacks_all(Bitboard::EMPTY),
+        Bitboard::EMPTY
+    );
+}
+
+#[test]
+fn subsets_of_a_three_bit_mask() {
+    let mask = squares(&["b2", "e4", "h8"]);
+    let subsets: Vec<Bitboard> = mask.subsets().collect();
+
+    assert_eq!(subsets.len(), 8);
+    assert_eq!(subsets.first(), Some(&Bitboard::EMPTY));
+    assert_eq!(subsets.last(), Some(&mask));
+    for (i, subset) in subsets.iter().enumerate() {
+        assert_eq!(*subset & !mask, Bitboard::EMPTY);
+        assert!(!subsets[..i].contains(subset));
+    }
+
+    assert_eq!(
+        Bitboard::EMPTY.subsets().collect::<Vec<_>>(),
+        [Bitboard::EMPTY]
+    );
+}
+
+// xorshift64, only needs to give varied bit patterns
+fn random_bitboards(count: usize) -> Vec<Bitboard> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..count)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            Bitboard(state)
+        })
+        .collect()
+}
+
+#[test]
+fn pdep_undoes_pext() {
+    let values = random_bitboards(200);
+    let masks = random_bitboards(50);
+    for &mask in masks.iter().chain(&[Bitboard::EMPTY, Bitboard::FULL]) {
+        for &x in &values {
+            let packed = x.pext(mask);
+            if mask != Bitboard::FULL {
+                assert_eq!(packed >> mask.count_pieces(), 0);
+            }
+            assert_eq!(Bitboard::pdep(packed, mask), x & mask);
+        }
+    }
+
+    // The same numbering generate_blocker_board uses
+    let mask = lemonate::generate_rook_mask(sq("d4"));
+    for index in 0..1 << mask.count_pieces() {
+        assert_eq!(
+            Bitboard::pdep(index as u64, mask),
+            lemonate::generate_blocker_board(index, mask)
+        );
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn pext_and_pdep_match_the_instructions() {
+    if !std::arch::is_x86_feature_detected!("bmi2") {
+        return;
+    }
+    let values = random_bitboards(100);
+    for &mask in &values {
+        for &x in &values[..20] {
+            // SAFETY: BMI2 was detected above
+            let (pext, pdep) = unsafe {
+                (
+                    std::arch::x86_64::_pext_u64(x.0, mask.0),
+                    std::arch::x86_64::_pdep_u64(x.0, mask.0),
+                )
+            };
+            assert_eq!(x.pext(mask), pext);
+            assert_eq!(Bitboard::pdep(x.0, mask), Bitboard(pdep));
+        }
+    }
+}
+
+#[test]
+fn ray_attacks_stop_at_the_first_blocker() {
+    let blockers = squares(&["d6", "d7", "b4"]);
+    assert_eq!(
+        Bitboard::ray_attacks(sq("d2"), Direction::NORTH, blockers),
+        squares(&["d3", "d4", "d5", "d6"])
+    );
+    assert_eq!(
+        Bitboard::ray_attacks(sq("d4"), Direction::WEST, blockers),
+        squares(&["c4", "b4"])
+    );
+
+    // Nothing in the way runs to the edge, without wrapping
+    assert_eq!(
+        Bitboard::ray_attacks(sq("a1"), Direction::NORTHEAST, Bitboard::EMPTY),
+        squares(&["b2", "c3", "d4", "e5", "f6", "g7", "h8"])
+    );
+    assert_eq!(
+        Bitboard::ray_attacks(sq("h3"), Direction::EAST, Bitboard::EMPTY),
+        Bitboard::EMPTY
+    );
+
+    // The union of the four orthogonal rays is the rook's attack set
+    for index in 0..64 {
+        let square = Square::from_index(index);
+        let rays = [
+            Direction::NORTH,
+            Direction::EAST,
+            Direction::SOUTH,
+            Direction::WEST,
+        ]
+        .iter()
+        .fold(Bitboard::EMPTY, |all, &dir| {
+            all | Bitboard::ray_attacks(square, dir, blockers)
+        });
+        assert_eq!(rays, attacks().rook_attacks(square, blockers));
+    }
+}
+
+#[test]
+fn bit_scans_agree_with_first_and_last_square() {
+    let boards = [
+        Bitboard(1),
+        Bitboard(1 << 63),
+        Bitboard::FULL,
+        Bitboard::rank(3),
+        Bitboard::file(5),
+        Bitboard(0x0040_0000_0000_0400),
+        Bitboard(0x8000_0000_0000_0001),
+    ];
+    for bb in boards {
+        assert_eq!(bb.bsf() as usize, bb.first_square().unwrap().index());
+        assert_eq!(bb.bsr() as usize, bb.last_square().unwrap().index());
+    }
+}
@@ -0,0 +1,302 @@
+mod common;
+
+use common::{KIWIPETE, START_FEN, attacks, board, play, sq};
+use lemonate::{
+    Board, BoardBuilder, CastlingRights, Color, Piece, PieceType, PositionError, Square,
+};
+use std::collections::HashSet;
+
+#[test]
+fn starting_position_matches_the_start_fen() {
+    let start = Board::starting_position();
+    let parsed = board(START_FEN);
+    // Debug shows every field, the hash included
+    assert_eq!(format!("{start:?}"), format!("{parsed:?}"));
+    assert_eq!(start.to_fen(), START_FEN);
+}
+
+#[test]
+fn place_then_remove_restores_the_board() {
+    let original = board(KIWIPETE);
+    let knight = Piece {
+        piece_type: PieceType::Knight,
+        color: Color::Black,
+    };
+    let is_knight = |piece: Option<Piece>| {
+        matches!(
+            piece,
+            Some(Piece {
+                piece_type: PieceType::Knight,
+                color: Color::Black
+            })
+        )
+    };
+
+    let mut b = original.clone();
+    b.place_piece(sq("d3"), knight);
+    assert!(is_knight(b.piece_at(sq("d3"))));
+    assert_ne!(format!("{b:?}"), format!("{original:?}"));
+
+    assert!(is_knight(b.remove_piece(sq("d3"))));
+    // Debug shows every bitboard and the hash
+    assert_eq!(format!("{b:?}"), format!("{original:?}"));
+    assert_eq!(b.to_fen(), KIWIPETE);
+}
+
+#[test]
+fn remove_from_an_empty_square_does_nothing() {
+    let mut b = board(KIWIPETE);
+    assert!(b.remove_piece(sq("d3")).is_none());
+    assert_eq!(format!("{b:?}"), format!("{:?}", board(KIWIPETE)));
+}
+
+#[test]
+#[allow(deprecated)]
+fn misspelled_peice_at_matches_piece_at() {
+    let b = board(KIWIPETE);
+    for index in 0..64 {
+        let square = Square::from_index(index);
+        assert_eq!(
+            format!("{:?}", b.peice_at(square)),
+            format!("{:?}", b.piece_at(square))
+        );
+    }
+}
+
+#[test]
+fn display_draws_the_start_position() {
+    let text = Board::starting_position().to_string();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[0], "8 r n b q k b n r");
+    assert_eq!(lines[7], "1 R N B Q K B N R");
+    assert_eq!(lines[8], "  a b c d e f g h");
+    assert_eq!(lines[9], "White to move, castling KQkq, en passant -");
+}
+
+#[test]
+fn display_shows_the_en_passant_square() {
+    let text = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b Kq e3 0 1").to_string();
+    assert_eq!(
+        text.lines().last().unwrap(),
+        "Black to move, castling Kq, en passant e3"
+    );
+}
+
+#[test]
+fn unicode_start_position_has_a_glyph_per_piece() {
+    let start = Board::starting_position();
+    let text = start.to_unicode();
+
+    let glyphs = text.chars().filter(|ch| !ch.is_whitespace()).count();
+    assert_eq!(glyphs, 32);
+    assert_eq!(text.lines().count(), 8);
+    assert_eq!(text.lines().next().unwrap(), "♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜");
+    assert_eq!(text.lines().last().unwrap(), "♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖");
+}
+
+#[test]
+fn unicode_from_black_rotates_the_board() {
+    let b = board(KIWIPETE);
+    let white: Vec<char> = b.to_unicode().chars().collect();
+    let black: Vec<char> = b.to_unicode_from(Color::Black).chars().collect();
+
+    // Every rank line has the same length, so reversing the whole text
+    // (newlines aside) turns the board around
+    let rotated: String = white.iter().rev().skip(1).chain(['\n'].iter()).collect();
+    assert_eq!(black.iter().collect::<String>(), rotated);
+}
+
+#[test]
+fn occupancy_accessors_follow_place_and_remove() {
+    let mut b = Board::starting_position();
+    let white = b.occupancy_of(Color::White);
+    let black = b.occupancy_of(Color::Black);
+    assert_eq!(white.count_pieces(), 16);
+    assert_eq!(black.count_pieces(), 16);
+    assert_eq!(b.occupancy(), white | black);
+    assert_eq!(b.pieces(Color::Black, PieceType::Knight).count_pieces(), 2);
+
+    let knight = Piece {
+        piece_type: PieceType::Knight,
+        color: Color::Black,
+    };
+    b.place_piece(sq("d4"), knight);
+    assert!(b.occupancy().is_set(sq("d4")));
+    assert!(b.occupancy_of(Color::Black).is_set(sq("d4")));
+    assert!(!b.occupancy_of(Color::White).is_set(sq("d4")));
+    assert!(b.pieces(Color::Black, PieceType::Knight).is_set(sq("d4")));
+
+    b.remove_piece(sq("d4"));
+    b.remove_piece(sq("e2"));
+    assert!(!b.occupancy().is_set(sq("d4")));
+    assert!(!b.pieces(Color::Black, PieceType::Knight).is_set(sq("d4")));
+    assert!(!b.occupancy_of(Color::White).is_set(sq("e2")));
+    assert!(!b.pieces(Color::White, PieceType::Pawn).is_set(sq("e2")));
+    assert_eq!(
+        b.occupancy(),
+        b.occupancy_of(Color::White) | b.occupancy_of(Color::Black)
+    );
+}
+
+#[test]
+fn king_square_follows_the_king() {
+    let mut b = Board::starting_position();
+    assert_eq!(b.king_square(Color::White), sq("e1"));
+    assert_eq!(b.king_square(Color::Black), sq("e8"));
+
+    play(&mut b, &["e2e4", "e7e5", "e1e2", "e8e7"]);
+    assert_eq!(b.king_square(Color::White), sq("e2"));
+    assert_eq!(b.king_square(Color::Black), sq("e7"));
+
+    let mut b = board("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    play(&mut b, &["e1g1", "e8c8"]);
+    assert_eq!(b.king_square(Color::White), sq("g1"));
+    assert_eq!(b.king_square(Color::Black), sq("c8"));
+}
+
+#[test]
+fn flip_mirrors_colors_and_ranks() {
+    let white_only = board("4k3/8/8/8/8/8/1P6/R3K2R w KQ - 3 20");
+    let flipped = white_only.flip();
+    assert_eq!(flipped.to_fen(), "r3k2r/1p6/8/8/8/8/8/4K3 b kq - 3 20");
+    assert_eq!(flipped.occupancy_of(Color::White).count_pieces(), 1);
+
+    let ep = board("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+    assert_eq!(ep.flip().to_fen(), "4k3/8/8/8/3Pp3/8/8/4K3 b - d3 0 1");
+}
+
+#[test]
+fn flipping_twice_gives_the_original() {
+    for fen in [START_FEN, KIWIPETE, "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1"] {
+        let b = board(fen);
+        let twice = b.flip().flip();
+        // Debug covers every bitboard and the hash
+        assert_eq!(format!("{twice:?}"), format!("{b:?}"));
+        assert_eq!(b.flip().hash(), b.flip().compute_hash());
+    }
+}
+
+#[test]
+fn equality_ignores_the_move_counters() {
+    let early = board("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+    let late = board("4k3/8/8/8/8/8/8/R3K2R w KQ - 12 40");
+    assert_eq!(early, late);
+
+    let mut seen = HashSet::new();
+    seen.insert(early);
+    assert!(seen.contains(&late));
+
+    assert_ne!(late, board("4k3/8/8/8/8/8/8/R3K2R b KQ - 12 40"));
+    assert_ne!(late, board("4k3/8/8/8/8/8/8/R3K2R w K - 12 40"));
+    assert!(!seen.contains(&board("4k3/8/8/8/8/8/8/R3K2R w K - 0 1")));
+
+    // Reached by different moves, so the histories differ too
+    let mut b = Board::starting_position();
+    play(&mut b, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(b, Board::starting_position());
+}
+
+fn piece(color: Color, piece_type: PieceType) -> Piece {
+    Piece { piece_type, color }
+}
+
+#[test]
+fn builder_sets_up_a_known_position() {
+    // 1.e4 c5 2.Nf3 with the black king and queenside rook gone home early
+    let built = BoardBuilder::new()
+        .piece(sq("e1"), piece(Color::White, PieceType::King))
+        .piece(sq("h1"), piece(Color::White, PieceType::Rook))
+        .piece(sq("f3"), piece(Color::White, PieceType::Knight))
+        .piece(sq("e4"), piece(Color::White, PieceType::Pawn))
+        .piece(sq("e8"), piece(Color::Black, PieceType::King))
+        .piece(sq("a8"), piece(Color::Black, PieceType::Rook))
+        .piece(sq("c5"), piece(Color::Black, PieceType::Pawn))
+        .side_to_move(Color::Black)
+        .castling(CastlingRights {
+            white_kingside: true,
+            white_queenside: false,
+            black_kingside: false,
+            black_queenside: true,
+        })
+        .halfmove(1)
+        .fullmove(2)
+        .build(attacks())
+        .unwrap();
+
+    let fen = "r3k3/8/8/2p5/4P3/5N2/8/4K2R b Kq - 1 2";
+    assert_eq!(built.to_fen(), fen);
+    assert_eq!(built, board(fen));
+    assert_eq!(built.hash(), board(fen).hash());
+}
+
+#[test]
+fn builder_replaces_pieces_and_sets_en_passant() {
+    let built = BoardBuilder::new()
+        .piece(sq("e1"), piece(Color::White, PieceType::King))
+        .piece(sq("e8"), piece(Color::Black, PieceType::King))
+        .piece(sq("d5"), piece(Color::Black, PieceType::Queen))
+        .piece(sq("d5"), piece(Color::Black, PieceType::Pawn))
+        .piece(sq("e5"), piece(Color::White, PieceType::Pawn))
+        .en_passant(sq("d6"))
+        .build(attacks())
+        .unwrap();
+
+    assert_eq!(built.to_fen(), "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+}
+
+#[test]
+fn builder_rejects_invalid_positions() {
+    let no_black_king = BoardBuilder::new()
+        .piece(sq("e1"), piece(Color::White, PieceType::King))
+        .build(attacks());
+    assert_eq!(
+        no_black_king.unwrap_err(),
+        PositionError::KingCount {
+            color: Color::Black,
+            count: 0
+        }
+    );
+
+    let rook_missing = BoardBuilder::new()
+        .piece(sq("e1"), piece(Color::White, PieceType::King))
+        .piece(sq("e8"), piece(Color::Black, PieceType::King))
+        .castling(CastlingRights::all())
+        .build(attacks());
+    assert!(matches!(
+        rook_missing,
+        Err(PositionError::InvalidCastlingRights(..))
+    ));
+}
+
+#[test]
+fn pieces_iter_visits_every_piece_once() {
+    let b = Board::starting_position();
+    let pieces: Vec<(Square, Piece)> = b.pieces_iter().collect();
+
+    assert_eq!(pieces.len(), 32);
+    assert!(pieces.contains(&(sq("e1"), piece(Color::White, PieceType::King))));
+    assert!(pieces.contains(&(sq("d8"), piece(Color::Black, PieceType::Queen))));
+    assert_eq!(pieces[0], (sq("a1"), piece(Color::White, PieceType::Rook)));
+    assert!(pieces.windows(2).all(|w| w[0].0.index() < w[1].0.index()));
+    assert_eq!(Board::new().pieces_iter().count(), 0);
+}
+
+#[test]
+fn piece_counts_at_the_start_and_after_a_capture() {
+    let mut b = Board::starting_position();
+    assert_eq!(b.piece_count(), 32);
+    for color in Color::ALL {
+        assert_eq!(b.occupancy_of(color).count_pieces(), 16);
+        assert_eq!(b.count(color, PieceType::Pawn), 8);
+        assert_eq!(b.count(color, PieceType::Knight), 2);
+        assert_eq!(b.count(color, PieceType::Queen), 1);
+        assert_eq!(b.count(color, PieceType::King), 1);
+    }
+
+    play(&mut b, &["e2e4", "d7d5", "e4d5"]);
+    assert_eq!(b.piece_count(), 31);
+    assert_eq!(b.count(Color::Black, PieceType::Pawn), 7);
+    assert_eq!(b.count(Color::White, PieceType::Pawn), 8);
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::{KIWIPETE, attacks, board, play, sq};
+use lemonate::{Move, Variant};
+
+// Rooks on b and g with the king between them on c
+const NRKBBQRN: &str = "nrkbbqrn/pppppppp/8/8/8/8/PPPPPPPP/NRKBBQRN w GBgb - 0 1";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let mut b = board(fen);
+    for (depth, &nodes) in (1..).zip(expected) {
+        assert_eq!(b.perft(depth, attacks()), nodes, "{fen} depth {depth}");
+    }
+}
+
+#[test]
+fn chess960_perft() {
+    assert_perft(
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        &[21, 528, 12189],
+    );
+    assert_perft(
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        &[21, 807, 18002],
+    );
+    assert_perft(
+        "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+        &[22, 593, 13440],
+    );
+}
+
+#[test]
+fn x_fen_letters_name_the_outermost_rook_with_the_king_on_e1() {
+    // The kings start on the e-file, but the rooks on b and f are still
+    // the ones KQkq stands for
+    let x_fen = "1rqbkrbn/1ppppp1p/1n6/p1N3p1/8/2P4P/PP1PPPP1/1RQBKRBN w KQkq - 0 9";
+    assert_eq!(board(x_fen), board(&x_fen.replace("KQkq", "FBfb")));
+    assert_eq!(board(x_fen).variant(), Variant::Chess960);
+    assert_perft(x_fen, &[29, 502, 14569, 287739]);
+}
+
+#[test]
+fn castling_from_a_chess960_start_where_the_king_stays_put() {
+    let mut b = board(NRKBBQRN);
+    assert_eq!(b.variant(), Variant::Chess960);
+
+    // Clearing d1 is all queenside castling needs: the king is already on
+    // c1, only the rook crosses over to d1
+    play(&mut b, &["e2e3", "e7e6", "d1e2", "d8e7"]);
+    let castle = Move::queen_castle(sq("c1"), sq("c1"));
+    assert!(b.generate_legal(attacks()).contains(&castle));
+    assert_eq!(b.parse_uci_move("c1b1"), Ok(castle));
+    assert_eq!(b.move_to_uci(castle), "c1b1");
+
+    let before = b.clone();
+    let undo = b.make_move(castle);
+    assert_eq!(
+        b.to_fen(),
+        "nrk1bqrn/ppppbppp/4p3/8/8/4P3/PPPPBPPP/N1KRBQRN b kq - 3 3"
+    );
+    assert_eq!(b.hash(), b.compute_hash());
+
+    b.unmake_move(castle, undo);
+    assert_eq!(b, before);
+    assert_eq!(b.to_fen(), before.to_fen());
+}
+
+#[test]
+fn castling_king_and_rook_swap_squares() {
+    let mut b = board("5k2/8/8/8/8/8/8/R4KR1 w GA - 0 1");
+    let castle = Move::king_castle(sq("f1"), sq("g1"));
+    assert!(b.generate_legal(attacks()).contains(&castle));
+    // The rook lands on the f-file the king was shielding
+    assert!(b.gives_check(castle, attacks()));
+
+    let before = b.clone();
+    let undo = b.make_move(castle);
+    assert_eq!(b.to_fen(), "5k2/8/8/8/8/8/8/R4RK1 b - - 1 1");
+    assert!(b.in_check(attacks()));
+
+    b.unmake_move(castle, undo);
+    assert_eq!(b.to_fen(), before.to_fen());
+}
+
+#[test]
+fn cannot_castle_into_a_check_the_rook_uncovers() {
+    // Once the b1 rook leaves for d1, the a1 rook sees the king on c1
+    let b = board("4k3/8/8/8/8/8/8/rRK2R2 w FB - 0 1");
+    let moves = b.generate_legal(attacks());
+    assert!(!moves.contains(&Move::queen_castle(sq("c1"), sq("c1"))));
+    assert!(moves.contains(&Move::king_castle(sq("c1"), sq("g1"))));
+}
+
+#[test]
+fn castling_rights_follow_the_rooks_that_hold_them() {
+    let mut b = board("4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1");
+    play(&mut b, &["g1g2"]);
+    // The a1 rook is still the outermost one, so X-FEN writes Q for it
+    assert_eq!(b.to_fen(), "4k3/8/8/8/8/8/6R1/R4K2 b Q - 1 1");
+}
+
+#[test]
+fn reads_shredder_and_x_fen_castling() {
+    let shredder = board(NRKBBQRN);
+    let x_fen = board(&NRKBBQRN.replace("GBgb", "KQkq"));
+    assert_eq!(shredder, x_fen);
+    assert_eq!(shredder.to_fen(), x_fen.to_fen());
+    assert!(shredder.to_fen().contains(" w KQkq "));
+
+    // Not the outermost rook, so only its file letter names it
+    let inner = "4k3/8/8/8/8/8/8/RR2K3 w B - 0 1";
+    let b = board(inner);
+    assert_eq!(b.variant(), Variant::Chess960);
+    assert_eq!(b.to_fen(), inner);
+    assert!(
+        b.generate_legal(attacks())
+            .contains(&Move::queen_castle(sq("e1"), sq("c1")))
+    );
+}
+
+#[test]
+fn standard_boards_keep_standard_notation() {
+    let b = board(KIWIPETE);
+    assert_eq!(b.variant(), Variant::Standard);
+    let castle = Move::king_castle(sq("e1"), sq("g1"));
+    assert_eq!(b.move_to_uci(castle), "e1g1");
+    assert_eq!(b.parse_uci_move("e1g1"), Ok(castle));
+    assert_eq!(board(&KIWIPETE.replace("KQkq", "HAha")).to_fen(), KIWIPETE);
+}
@@ -0,0 +1,46 @@
+// Shared by the integration tests, not every test file uses everything
+#![allow(dead_code)]
+
+use lemonate::{AttackTable, Board, Move, Square};
+use std::sync::OnceLock;
+
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+pub const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+/// Building the tables takes a moment in debug builds, so every test in a
+/// binary shares one.
+pub fn attacks() -> &'static AttackTable {
+    static ATTACKS: OnceLock<AttackTable> = OnceLock::new();
+    ATTACKS.get_or_init(AttackTable::new)
+}
+
+pub fn sq(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+/// Debug output up to the move history, i.e. the position, the state and
+/// the incrementally updated hash, for boards reached by different moves.
+pub fn position(board: &Board) -> String {
+    let debug = format!("{board:?}");
+    debug[..debug.find(", history").unwrap()].to_string()
+}
+
+pub fn board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap()
+}
+
+/// Parses and plays UCI moves, panicking on anything illegal.
+pub fn play(board: &mut Board, moves: &[&str]) {
+    for &uci in moves {
+        let mv = uci_move(board, uci);
+        board.make_move(mv);
+    }
+}
+
+pub fn uci_move(board: &Board, uci: &str) -> Move {
+    board
+        .generate_legal(attacks())
+        .into_iter()
+        .find(|mv| mv.to_string() == uci)
+        .unwrap_or_else(|| panic!("{uci} is illegal"))
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{attacks, sq};
+use lemonate::epd::{self, EpdError};
+use lemonate::{FenError, Move, SanError};
+
+#[test]
+fn wac_line_with_best_move_and_id() {
+    let line = r#"2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";"#;
+    let record = epd::parse(line, attacks()).unwrap();
+
+    assert_eq!(
+        record.board.to_fen(),
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+    );
+    assert_eq!(record.operations["id"], "WAC.001");
+    assert_eq!(record.operations["bm"], "Qg6");
+    assert_eq!(record.best_moves, [Move::quiet(sq("g3"), sq("g6"))]);
+    assert!(record.avoid_moves.is_empty());
+}
+
+#[test]
+fn several_operands_and_quoted_semicolons() {
+    let line = r#"4k3/8/8/8/8/8/4P3/4K3 w - - am e3 e4; c0 "pawn; push" ; id "x""#;
+    let record = epd::parse(line, attacks()).unwrap();
+
+    assert_eq!(record.operations["am"], "e3 e4");
+    assert_eq!(record.operations["c0"], "pawn; push");
+    assert_eq!(record.operations["id"], "x");
+    assert_eq!(
+        record.avoid_moves,
+        [
+            Move::quiet(sq("e2"), sq("e3")),
+            Move::double_pawn_push(sq("e2"), sq("e4"))
+        ]
+    );
+}
+
+#[test]
+fn bad_records_are_rejected() {
+    assert!(matches!(
+        epd::parse("4k3/8/8/8/8/8/8/4K3 x - - id \"a\";", attacks()),
+        Err(EpdError::InvalidPosition(FenError::InvalidActiveColor))
+    ));
+    assert!(matches!(
+        epd::parse("4k3/8/8/8/8/8/8/4K3 w - - ; id \"a\";", attacks()),
+        Err(EpdError::InvalidOperation)
+    ));
+    assert!(matches!(
+        epd::parse("4k3/8/8/8/8/8/8/4K3 w - - id \"a;", attacks()),
+        Err(EpdError::InvalidOperation)
+    ));
+    assert!(matches!(
+        epd::parse("4k3/8/8/8/8/8/8/4K3 w - - bm Qh5;", attacks()),
+        Err(EpdError::InvalidMove {
+            error: SanError::IllegalMove,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{KIWIPETE, board, sq};
+use lemonate::{Bitboard, Board, Color, PieceSquareTable, PieceType};
+
+#[test]
+fn start_position_is_balanced() {
+    assert_eq!(Board::starting_position().material_balance(), 0);
+}
+
+#[test]
+fn losing_a_knight_shows_in_the_balance() {
+    let mut b = Board::starting_position();
+    b.remove_piece(sq("g1"));
+    assert_eq!(b.material_balance(), -PieceType::KNIGHT_VALUE);
+
+    b.remove_piece(sq("d8"));
+    assert_eq!(
+        b.material_balance(),
+        PieceType::QUEEN_VALUE - PieceType::KNIGHT_VALUE
+    );
+}
+
+#[test]
+fn kings_are_not_counted() {
+    assert_eq!(board("4k3/8/8/8/8/8/8/4K3 w - - 0 1").material_balance(), 0);
+    assert_eq!(PieceType::King.value(), 0);
+}
+
+#[test]
+fn pawn_structure() {
+    // White: doubled c-pawns and an a-pawn with no neighbours, both far
+    // from any black pawn. Black: a doubled h-pair with the g-pawn gone.
+    let b = board("4k3/5p1p/7p/4P3/8/2P5/P1P5/4K3 w - - 0 1");
+    let squares = |names: &[&str]| {
+        names.iter().fold(Bitboard::EMPTY, |all, name| {
+            all | Bitboard::from_square(sq(name))
+        })
+    };
+
+    assert_eq!(b.doubled_pawns(Color::White), squares(&["c2", "c3"]));
+    assert_eq!(
+        b.isolated_pawns(Color::White),
+        squares(&["a2", "c2", "c3", "e5"])
+    );
+    // f7 guards e6 so e5 is not passed, nothing black stands in front of
+    // the queenside pawns
+    assert_eq!(b.passed_pawns(Color::White), squares(&["a2", "c2", "c3"]));
+
+    assert_eq!(b.doubled_pawns(Color::Black), squares(&["h6", "h7"]));
+    assert_eq!(b.isolated_pawns(Color::Black), squares(&["f7", "h6", "h7"]));
+    assert_eq!(b.passed_pawns(Color::Black), squares(&["h6", "h7"]));
+
+    // A pawn level with an enemy on the next file is still passed
+    let level = board("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1");
+    assert_eq!(level.passed_pawns(Color::White), squares(&["e5"]));
+    assert_eq!(level.passed_pawns(Color::Black), squares(&["d5"]));
+}
+
+#[test]
+fn phase_runs_from_opening_to_endgame() {
+    assert_eq!(Board::starting_position().phase(), Board::MAX_PHASE);
+    assert_eq!(board("4k3/pp6/8/8/8/8/5PPP/4K3 w - - 0 1").phase(), 0);
+    // A rook and a knight each
+    assert_eq!(board("3rk3/5n2/8/8/8/8/8/1N1RK3 w - - 0 1").phase(), 6);
+    // Three queens still stop at the cap
+    assert_eq!(
+        board("rnbqkbnr/pppppppp/8/8/8/8/QQPPPPPP/RNBQKBNR w KQkq - 0 1").phase(),
+        Board::MAX_PHASE
+    );
+}
+
+#[test]
+fn psqt_score_is_symmetric_under_flip() {
+    let tables = PieceSquareTable::default();
+    assert_eq!(Board::starting_position().psqt_score(&tables), 0);
+
+    let b = board(KIWIPETE);
+    assert_eq!(b.flip().psqt_score(&tables), -b.psqt_score(&tables));
+
+    // A knight in the centre beats one in the corner, for either color
+    let centre = board("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1");
+    let corner = board("4k3/8/8/8/8/8/8/N3K3 w - - 0 1");
+    assert!(centre.psqt_score(&tables) > corner.psqt_score(&tables));
+    assert!(centre.flip().psqt_score(&tables) < corner.flip().psqt_score(&tables));
+
+    // e4 is on rank 4 of white's side, e5 the same for black
+    assert_eq!(
+        tables.value(Color::White, PieceType::Pawn, sq("e4")),
+        tables.value(Color::Black, PieceType::Pawn, sq("e5"))
+    );
+    assert_eq!(tables.value(Color::White, PieceType::Pawn, sq("e4")), 20);
+}
+
+#[test]
+fn side_to_move_perspective_puts_white_to_move() {
+    // Black to move and a rook up
+    let b = board("r3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+    assert!(b.material_balance() < 0);
+
+    let view = b.side_to_move_perspective();
+    assert_eq!(view.to_fen(), "4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+    assert_eq!(view.material_balance(), -b.material_balance());
+
+    let white = board(KIWIPETE);
+    assert_eq!(white.side_to_move_perspective().to_fen(), KIWIPETE);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use common::{KIWIPETE, START_FEN, board, sq};
+use lemonate::{Board, Color, FenError, Piece, PieceType, Square};
+
+#[test]
+fn parses_the_start_position() {
+    let b = board(START_FEN);
+    assert!(matches!(
+        b.piece_at(sq("e1")),
+        Some(Piece {
+            piece_type: PieceType::King,
+            color: Color::White
+        })
+    ));
+    assert!(matches!(
+        b.piece_at(sq("d8")),
+        Some(Piece {
+            piece_type: PieceType::Queen,
+            color: Color::Black
+        })
+    ));
+    assert!(b.piece_at(sq("e4")).is_none());
+    let occupied = (0..64)
+        .filter(|&index| b.piece_at(Square::from_index(index)).is_some())
+        .count();
+    assert_eq!(occupied, 32);
+}
+
+#[test]
+fn tactical_positions_round_trip() {
+    for fen in [
+        START_FEN,
+        KIWIPETE,
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ] {
+        assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+    }
+}
+
+#[test]
+fn move_counters_are_optional() {
+    let b = board("8/8/8/8/8/8/8/K6k w - -");
+    assert!(b.to_fen().ends_with(" 0 1"));
+}
+
+#[test]
+fn rejects_truncated_fens() {
+    assert!(matches!(Board::from_fen(""), Err(FenError::InvalidFormat)));
+    assert!(matches!(
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq"),
+        Err(FenError::InvalidFormat)
+    ));
+    assert!(matches!(
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1"),
+        Err(FenError::InvalidPiecePlacement)
+    ));
+    assert!(matches!(
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBN w KQkq - 0 1"),
+        Err(FenError::InvalidPiecePlacement)
+    ));
+}
+
+#[test]
+fn rejects_overfull_ranks() {
+    for placement in [
+        "rnbqkbnrr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR",
+        "rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR",
+        "rnbqkbnr/pppppppp/8/8/4P4/8/PPPPPPPP/RNBQKBNR",
+        "rnbqkbnr/pppppppp/8/8/62P/8/PPPPPPPP/RNBQKBNR",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/8",
+    ] {
+        let fen = format!("{placement} w KQkq - 0 1");
+        assert!(
+            matches!(Board::from_fen(&fen), Err(FenError::InvalidPiecePlacement)),
+            "{fen}"
+        );
+    }
+}
+
+#[test]
+fn rejects_bad_fields() {
+    let with = |side, castling, ep, half, full| {
+        Board::from_fen(&format!(
+            "4k3/8/8/8/8/8/8/4K2R {side} {castling} {ep} {half} {full}"
+        ))
+    };
+    assert!(matches!(
+        with("x", "K", "-", "0", "1"),
+        Err(FenError::InvalidActiveColor)
+    ));
+    assert!(matches!(
+        with("w", "KX", "-", "0", "1"),
+        Err(FenError::InvalidCastlingRights)
+    ));
+    assert!(matches!(
+        with("w", "K", "e4", "0", "1"),
+        Err(FenError::InvalidEnPassant)
+    ));
+    // The rank has to match the side that can capture
+    assert!(matches!(
+        with("w", "K", "e3", "0", "1"),
+        Err(FenError::InvalidEnPassant)
+    ));
+    assert!(matches!(
+        with("b", "K", "e6", "0", "1"),
+        Err(FenError::InvalidEnPassant)
+    ));
+    assert!(matches!(
+        with("w", "K", "-", "x", "1"),
+        Err(FenError::InvalidHalfMove)
+    ));
+    assert!(matches!(
+        with("w", "K", "-", "0", "-1"),
+        Err(FenError::InvalidFullMove)
+    ));
+    assert!(matches!(
+        Board::from_fen("4k3/8/8/8/8/8/8/4K2X w - - 0 1"),
+        Err(FenError::InvalidPiece)
+    ));
+}
+
+#[test]
+fn to_fen_round_trips_en_passant_and_counters() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 17 42",
+        "8/8/8/8/8/8/8/K6k b - - 99 120",
+    ] {
+        assert_eq!(board(fen).to_fen(), fen);
+    }
+}
+
+#[test]
+fn to_fen_after_moves() {
+    let mut b = Board::starting_position();
+    common::play(&mut b, &["e2e4", "c7c5", "g1f3"]);
+    assert_eq!(
+        b.to_fen(),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+    );
+}
+
+#[test]
+fn verbose_errors_point_at_the_bad_field() {
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQxq - 0 1";
+    let (error, offset) = Board::from_fen_verbose(fen).unwrap_err();
+    assert!(matches!(error, FenError::InvalidCastlingRights));
+    assert_eq!(&fen[offset..offset + 4], "KQxq");
+
+    let fen = "8/8/8/8/8/8/8/8   x - - 0 1";
+    assert!(matches!(
+        Board::from_fen_verbose(fen),
+        Err((FenError::InvalidActiveColor, 18))
+    ));
+
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 one";
+    let (error, offset) = Board::from_fen_verbose(fen).unwrap_err();
+    assert!(matches!(error, FenError::InvalidFullMove));
+    assert_eq!(&fen[offset..], "one");
+
+    let fen = "8/8/8/8/8/8/8/8 w -";
+    assert!(matches!(
+        Board::from_fen_verbose(fen),
+        Err((FenError::InvalidFormat, 19))
+    ));
+
+    let verbose = Board::from_fen_verbose(KIWIPETE).unwrap();
+    assert_eq!(verbose.to_fen(), KIWIPETE);
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::{board, uci_move};
+use lemonate::Board;
+use lemonate::game::Game;
+
+#[test]
+fn popping_every_move_returns_to_the_start() {
+    let mut game = Game::default();
+    for uci in ["e2e4", "e7e5", "g1f3"] {
+        let mv = uci_move(game.current(), uci);
+        game.push(mv);
+    }
+    let played: Vec<String> = game.moves().iter().map(|mv| mv.to_string()).collect();
+    assert_eq!(played, ["e2e4", "e7e5", "g1f3"]);
+
+    assert_eq!(game.pop().map(|mv| mv.to_string()).as_deref(), Some("g1f3"));
+    assert!(game.pop().is_some());
+    assert!(game.pop().is_some());
+    assert!(game.pop().is_none());
+
+    assert!(game.moves().is_empty());
+    // Debug covers the counters and history that Eq ignores
+    let start = Board::starting_position();
+    assert_eq!(format!("{:?}", game.current()), format!("{start:?}"));
+}
+
+#[test]
+fn game_tracks_repetitions_through_the_board() {
+    let mut game = Game::new(board("4k3/8/8/8/8/8/8/R3K3 w - - 0 1"));
+    for _ in 0..2 {
+        for uci in ["e1d1", "e8d8", "d1e1", "d8e8"] {
+            let mv = uci_move(game.current(), uci);
+            game.push(mv);
+        }
+    }
+    assert!(game.current().is_threefold_repetition());
+
+    game.pop();
+    assert!(!game.current().is_threefold_repetition());
+}
@@ -0,0 +1,234 @@
+mod common;
+
+use common::{attacks, sq};
+use lemonate::{
+    AttackTable, BISHOP_MAGICS, Bitboard, DEFAULT_MAGIC_SEED, Magic, MagicError, ROOK_MAGICS,
+    Square, calculate_bishop_attacks, calculate_rook_attacks, find_magic, find_magic_with_limit,
+    find_magic_with_seed, generate_bishop_mask, generate_blocker_board, generate_rook_mask,
+    init_bishop_magics_with_seed,
+};
+
+// xorshift64, only needs to give varied occupancies
+fn random_occupancies(count: usize) -> Vec<Bitboard> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    (0..count)
+        .map(|_| {
+            let mut next = || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            };
+            // Sparse boards, like real positions, as well as dense ones
+            Bitboard(next() & next())
+        })
+        .collect()
+}
+
+#[test]
+fn pext_and_magic_lookups_agree() {
+    let pext = attacks();
+    let magic = AttackTable::with_pext(false);
+    assert!(!magic.uses_pext());
+
+    for name in ["a1", "h1", "d4", "e5", "b7", "h8", "g2", "c6"] {
+        let square = sq(name);
+        for blockers in random_occupancies(500) {
+            let rook = magic.rook_attacks(square, blockers);
+            let bishop = magic.bishop_attacks(square, blockers);
+
+            assert_eq!(pext.rook_attacks(square, blockers), rook, "{name}");
+            assert_eq!(pext.bishop_attacks(square, blockers), bishop, "{name}");
+            assert_eq!(rook, calculate_rook_attacks(square, blockers), "{name}");
+            assert_eq!(bishop, calculate_bishop_attacks(square, blockers), "{name}");
+        }
+    }
+}
+
+#[test]
+fn embedded_magics_are_collision_free_on_every_square() {
+    let table = AttackTable::with_pext(false);
+
+    for index in 0..64 {
+        let square = Square::from_index(index);
+        let rook = table.rook_magics[index];
+        let bishop = table.bishop_magics[index];
+        assert_eq!(rook.magic, ROOK_MAGICS[index]);
+        assert_eq!(bishop.magic, BISHOP_MAGICS[index]);
+
+        // Every blocker pattern has to come back with its own attacks
+        for pattern in 0..rook.table_size() {
+            let blockers = generate_blocker_board(pattern, rook.mask);
+            assert_eq!(
+                table.rook_attacks(square, blockers),
+                calculate_rook_attacks(square, blockers),
+                "rook {square}"
+            );
+        }
+        for pattern in 0..bishop.table_size() {
+            let blockers = generate_blocker_board(pattern, bishop.mask);
+            assert_eq!(
+                table.bishop_attacks(square, blockers),
+                calculate_bishop_attacks(square, blockers),
+                "bishop {square}"
+            );
+        }
+    }
+}
+
+#[test]
+fn tiny_attempt_limit_returns_an_error() {
+    let square = sq("d4");
+    let mask = generate_rook_mask(square);
+
+    assert_eq!(
+        find_magic_with_limit(square, mask, true, 10),
+        Err(MagicError::TooManyAttempts { square, bits: 10 })
+    );
+}
+
+#[test]
+fn found_magic_is_collision_free() {
+    let square = sq("c1");
+    let mask = generate_bishop_mask(square);
+    let magic = find_magic(square, mask, false).unwrap();
+
+    let mut table = vec![None; 1 << mask.count_pieces()];
+    for pattern in 0..table.len() {
+        let blockers = generate_blocker_board(pattern, mask);
+        let index =
+            ((blockers.0 & mask.0).wrapping_mul(magic) >> (64 - mask.count_pieces())) as usize;
+        let attacks = calculate_bishop_attacks(square, blockers);
+        assert!(table[index].is_none_or(|stored| stored == attacks));
+        table[index] = Some(attacks);
+    }
+}
+
+#[test]
+fn same_seed_finds_the_same_magic() {
+    let square = sq("e4");
+    let mask = generate_rook_mask(square);
+
+    let first = find_magic_with_seed(square, mask, true, 0xdead_beef).unwrap();
+    let second = find_magic_with_seed(square, mask, true, 0xdead_beef).unwrap();
+    assert_eq!(first, second);
+
+    // The unseeded search is the default seed offset by the square
+    assert_eq!(
+        find_magic(square, mask, true),
+        find_magic_with_seed(
+            square,
+            mask,
+            true,
+            DEFAULT_MAGIC_SEED + square.index() as u64
+        )
+    );
+}
+
+#[test]
+fn default_seed_reproduces_the_embedded_bishop_table() {
+    let magics = init_bishop_magics_with_seed(DEFAULT_MAGIC_SEED).unwrap();
+    for (magic, &embedded) in magics.iter().zip(BISHOP_MAGICS.iter()) {
+        assert_eq!(magic.magic, embedded);
+    }
+}
+
+#[test]
+fn empty_mask_hashes_to_zero() {
+    // No relevant bits means a shift of 64, which would overflow
+    let magic = Magic::new(Bitboard::EMPTY, ROOK_MAGICS[0], 0);
+    assert_eq!(magic.shift, 64);
+    assert_eq!(magic.table_size(), 1);
+    assert_eq!(magic.hash(Bitboard::FULL), 0);
+    assert_eq!(magic.hash(Bitboard::EMPTY), 0);
+}
+
+#[test]
+fn seeded_search_matches_searching_each_square_alone() {
+    // Under the `parallel` feature this compares the threaded search with
+    // the sequential single-square one
+    let seed = 0xdead_beef;
+    let magics = init_bishop_magics_with_seed(seed).unwrap();
+    for (index, magic) in magics.iter().enumerate() {
+        let square = Square::from_index(index);
+        let mask = generate_bishop_mask(square);
+        let alone = find_magic_with_seed(square, mask, false, seed + index as u64).unwrap();
+        assert_eq!(magic.magic, alone, "square {index}");
+    }
+}
+
+#[test]
+fn subsets_match_index_based_blocker_boards() {
+    for mask in [
+        generate_rook_mask(sq("a1")),
+        generate_rook_mask(sq("d4")),
+        generate_bishop_mask(sq("e5")),
+    ] {
+        let by_index: Vec<Bitboard> = (0..1 << mask.count_pieces())
+            .map(|index| generate_blocker_board(index, mask))
+            .collect();
+        let by_subset: Vec<Bitboard> = mask.subsets().collect();
+        // Same boards in the same order, so table slots line up too
+        assert_eq!(by_subset, by_index);
+    }
+}
+
+#[test]
+fn magic_debug_and_display_show_the_derived_sizes() {
+    let rook = Magic::new(generate_rook_mask(sq("a1")), ROOK_MAGICS[0], 4096);
+
+    let debug = format!("{rook:?}");
+    assert!(debug.contains("magic: 0x2180004000208112"), "{debug}");
+    assert!(debug.contains("mask_bits: 12"), "{debug}");
+    assert!(debug.contains("shift: 52"), "{debug}");
+    assert!(debug.contains("table_size: 4096"), "{debug}");
+    assert!(debug.contains("offset: 4096"), "{debug}");
+
+    let bishop = Magic::new(generate_bishop_mask(sq("d4")), BISHOP_MAGICS[27], 0);
+    assert_eq!(
+        bishop.to_string(),
+        format!(
+            "magic {:#018x}, 9 mask bits, shift 55, 512 entries at offset 0",
+            BISHOP_MAGICS[27]
+        )
+    );
+}
+
+#[test]
+fn verify_accepts_embedded_magics_and_rejects_broken_ones() {
+    for name in ["a1", "e4", "h8"] {
+        let square = sq(name);
+        let index = square.index();
+        let rook = Magic::new(generate_rook_mask(square), ROOK_MAGICS[index], 0);
+        let bishop = Magic::new(generate_bishop_mask(square), BISHOP_MAGICS[index], 0);
+        assert!(rook.verify(square, true), "rook {name}");
+        assert!(bishop.verify(square, false), "bishop {name}");
+    }
+
+    let square = sq("d4");
+    let mask = generate_rook_mask(square);
+    // Every pattern lands in slot 0
+    assert!(!Magic::new(mask, 0, 0).verify(square, true));
+    // A good magic for the wrong square or piece
+    assert!(!Magic::new(mask, ROOK_MAGICS[0], 0).verify(square, true));
+    assert!(!Magic::new(generate_bishop_mask(square), ROOK_MAGICS[27], 0).verify(square, false));
+
+    // Too small a shift indexes past the table
+    let mut short = Magic::new(mask, ROOK_MAGICS[27], 0);
+    short.shift -= 1;
+    assert!(!short.verify(square, true));
+}
+
+#[test]
+fn stats_count_the_slider_tables() {
+    let table = attacks();
+    let stats = table.stats();
+
+    assert_eq!(stats.rook_entries, table.rook_attacks.len());
+    assert_eq!(stats.bishop_entries, table.bishop_attacks.len());
+    assert_eq!(stats.rook_entries, 102_400);
+    assert_eq!(stats.bishop_entries, 5_248);
+    // A rook in the corner sees 12 relevant squares
+    assert_eq!(stats.largest_table, 4096);
+    assert!(stats.memory_bytes > (stats.rook_entries + stats.bishop_entries) * 8);
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use common::{KIWIPETE, START_FEN, attacks, board, position, sq};
+use lemonate::{Board, Color, Move, Piece, PieceType, Square, ZobristKeys};
+
+#[test]
+fn e4_sets_en_passant_square_and_hash() {
+    let mut b = Board::starting_position();
+    b.make_move(Move::double_pawn_push(sq("e2"), sq("e4")));
+
+    assert!(b.piece_at(sq("e2")).is_none());
+    assert!(matches!(
+        b.piece_at(sq("e4")),
+        Some(Piece {
+            piece_type: PieceType::Pawn,
+            color: Color::White
+        })
+    ));
+
+    // The en passant square and the incrementally updated hash
+    let expected = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+    assert_eq!(position(&b), position(&expected));
+    assert_eq!(b.to_fen(), expected.to_fen());
+}
+
+#[test]
+fn en_passant_square_after_e4_and_e5() {
+    let mut b = Board::starting_position();
+    assert_eq!(b.en_passant_square(), None);
+
+    // Set even though no black pawn can take on e3
+    b.make_move(Move::double_pawn_push(sq("e2"), sq("e4")));
+    assert_eq!(b.en_passant_square(), Some(sq("e3")));
+
+    b.make_move(Move::double_pawn_push(sq("e7"), sq("e5")));
+    assert_eq!(b.en_passant_square(), Some(sq("e6")));
+
+    b.make_move(Move::quiet(sq("g1"), sq("f3")));
+    assert_eq!(b.en_passant_square(), None);
+}
+
+#[test]
+fn ep_target_is_the_skipped_square() {
+    assert_eq!(Square::ep_target(sq("e4"), Color::White), sq("e3"));
+    assert_eq!(Square::ep_target(sq("a5"), Color::Black), sq("a6"));
+}
+
+// Everything `make_move` can touch, Debug shows the bitboards, state and hash
+fn assert_same(a: &Board, b: &Board) {
+    assert_eq!(format!("{a:?}"), format!("{b:?}"));
+    assert_eq!(a.to_fen(), b.to_fen());
+}
+
+#[test]
+fn unmake_restores_every_kind_of_move() {
+    let cases = [
+        (START_FEN, Move::quiet(sq("g1"), sq("f3"))),
+        (START_FEN, Move::double_pawn_push(sq("e2"), sq("e4"))),
+        (KIWIPETE, Move::capture(sq("e5"), sq("f7"))),
+        (KIWIPETE, Move::king_castle(sq("e1"), sq("g1"))),
+        (KIWIPETE, Move::queen_castle(sq("e1"), sq("c1"))),
+        (KIWIPETE, Move::quiet(sq("h1"), sq("f1"))),
+        (
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            Move::promotion_capture(sq("a7"), sq("b8"), PieceType::Queen),
+        ),
+        (
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1",
+            Move::promotion(sq("b2"), sq("b1"), PieceType::Knight),
+        ),
+        (
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            Move::en_passant(sq("e5"), sq("f6")),
+        ),
+    ];
+    for (fen, mv) in cases {
+        let original = board(fen);
+        let mut b = original.clone();
+        let undo = b.make_move(mv);
+        b.unmake_move(mv, undo);
+        assert_same(&b, &original);
+    }
+}
+
+#[test]
+fn unmake_restores_random_games() {
+    // xorshift, fixed seed so failures reproduce
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for start in [START_FEN, KIWIPETE] {
+        for _ in 0..20 {
+            let mut b = board(start);
+            let mut stack = Vec::new();
+
+            for _ in 0..60 {
+                let legal = b.generate_legal(attacks());
+                if legal.is_empty() {
+                    break;
+                }
+                let mv = legal[next() as usize % legal.len()];
+                let before = b.clone();
+                let undo = b.make_move(mv);
+                stack.push((before, mv, undo));
+            }
+
+            while let Some((before, mv, undo)) = stack.pop() {
+                b.unmake_move(mv, undo);
+                assert_same(&b, &before);
+            }
+            assert_same(&b, &board(start));
+        }
+    }
+}
+
+#[test]
+fn king_move_clears_only_its_own_rights() {
+    let mut b = board("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    b.make_move(Move::quiet(sq("e1"), sq("e2")));
+    assert_eq!(b.to_fen(), "r3k2r/8/8/8/8/8/4K3/R6R b kq - 1 1");
+}
+
+#[test]
+fn move_counters_stop_at_their_maximum() {
+    let fen = "4k3/8/8/8/8/8/8/4K3 b - - 65535 65535";
+    let mut b = board(fen);
+    let mv = Move::quiet(sq("e8"), sq("d8"));
+    let undo = b.make_move(mv);
+    assert_eq!(b.to_fen(), "3k4/8/8/8/8/8/8/4K3 w - - 65535 65535");
+
+    b.unmake_move(mv, undo);
+    assert_eq!(b.to_fen(), fen);
+}
+
+#[test]
+fn null_move_flips_side_and_clears_en_passant() {
+    let mut b = Board::starting_position();
+    b.make_move(Move::double_pawn_push(sq("e2"), sq("e4")));
+    let before = b.clone();
+    let keys = ZobristKeys::get();
+
+    let undo = b.make_null_move();
+    assert_eq!(
+        b.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 1"
+    );
+    assert_eq!(
+        b.hash(),
+        before.hash() ^ keys.side ^ keys.en_passant[sq("e3").file() as usize]
+    );
+    assert_eq!(b.hash(), b.compute_hash());
+
+    b.unmake_null_move(undo);
+    assert_same(&b, &before);
+    assert_eq!(b.hash(), before.hash());
+}
+
+#[test]
+fn null_move_without_en_passant_only_flips_side_hash() {
+    let mut b = board(KIWIPETE);
+    let before = b.clone();
+
+    let undo = b.make_null_move();
+    assert!(b.to_fen().contains(" b KQkq - 1 1"));
+    assert_eq!(b.hash(), before.hash() ^ ZobristKeys::get().side);
+
+    b.unmake_null_move(undo);
+    assert_same(&b, &before);
+    assert_eq!(b.hash(), before.hash());
+}
+
+#[test]
+fn null_move_halfmove_clock_stops_at_its_maximum() {
+    let mut b = board("4k3/8/8/8/8/8/8/4K3 w - - 65535 1");
+    let undo = b.make_null_move();
+    assert_eq!(b.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 65535 1");
+    b.unmake_null_move(undo);
+    assert_eq!(b.to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 65535 1");
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::{KIWIPETE, START_FEN, attacks, board, sq};
+use lemonate::{Board, Move, MoveList, PieceType};
+
+#[test]
+fn pseudo_legal_counts() {
+    let start = Board::starting_position();
+    let moves = start.generate_pseudo_legal(attacks());
+    assert_eq!(moves.len(), 20);
+    let knight_moves = moves
+        .iter()
+        .filter(|mv| {
+            matches!(
+                start.piece_at(mv.from()).unwrap().piece_type,
+                PieceType::Knight
+            )
+        })
+        .count();
+    assert_eq!(knight_moves, 4);
+
+    assert_eq!(board(KIWIPETE).generate_pseudo_legal(attacks()).len(), 48);
+    assert_eq!(
+        board("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8")
+            .generate_pseudo_legal(attacks())
+            .len(),
+        44
+    );
+}
+
+#[test]
+fn pseudo_legal_includes_moves_that_leave_the_king_in_check() {
+    let b = board("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1");
+    let pseudo: Vec<String> = b
+        .generate_pseudo_legal(attacks())
+        .iter()
+        .map(|mv| mv.to_string())
+        .collect();
+    assert_eq!(pseudo.len(), 16);
+    // Into the c7 pawn's attack, and unblocking the h5 rook
+    assert!(pseudo.contains(&"a5b6".to_string()));
+    assert!(pseudo.contains(&"b5b6".to_string()));
+
+    let legal = b.generate_legal(attacks());
+    assert_eq!(legal.len(), 14);
+    assert!(legal.iter().all(|mv| mv.from() != sq("b5")));
+}
+
+#[test]
+fn captures_match_the_noisy_legal_moves() {
+    for fen in [
+        KIWIPETE,
+        // En passant, promotions with and without capture, and a pin
+        "r3k3/1P6/8/3pP3/8/8/6p1/4K2R w K d6 0 1",
+        "4k3/8/8/1b6/8/3N4/8/1K2R3 w - - 0 1",
+    ] {
+        let b = board(fen);
+        let mut expected: Vec<String> = b
+            .generate_legal(attacks())
+            .into_iter()
+            .filter(|mv| mv.is_capture() || mv.is_promotion())
+            .map(|mv| mv.to_string())
+            .collect();
+        let mut captures: Vec<String> = b
+            .generate_captures(attacks())
+            .into_iter()
+            .map(|mv| mv.to_string())
+            .collect();
+
+        expected.sort();
+        captures.sort();
+        assert!(!captures.is_empty(), "{fen}");
+        assert_eq!(captures, expected, "{fen}");
+    }
+}
+
+#[test]
+fn move_list_matches_the_vec_generator() {
+    let mut list = MoveList::new();
+    for fen in [
+        START_FEN,
+        KIWIPETE,
+        "r3k3/1P6/8/3pP3/8/8/6p1/4K2R w K d6 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ] {
+        let b = board(fen);
+        let vec = b.generate_legal(attacks());
+
+        b.generate_legal_into(&mut list, attacks());
+        assert_eq!(list.as_slice(), vec.as_slice(), "{fen}");
+        assert_eq!(list.len(), vec.len());
+        for (i, mv) in list.iter().enumerate() {
+            assert_eq!(list[i], *mv);
+        }
+    }
+}
+
+#[test]
+fn move_list_retain_keeps_order() {
+    let mut list = MoveList::new();
+    assert!(list.is_empty());
+    for mv in Board::starting_position().generate_pseudo_legal(attacks()) {
+        list.push(mv);
+    }
+    list.retain(|mv| mv.from().rank() == 1);
+
+    let kept: Vec<String> = (&list).into_iter().map(|mv| mv.to_string()).collect();
+    assert_eq!(kept.len(), 16);
+    assert_eq!(kept[0], "a2a3");
+    assert!(list.contains(&list[15]));
+
+    list.clear();
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn is_legal_checks_a_single_move() {
+    let start = Board::starting_position();
+    assert!(start.is_legal(Move::quiet(sq("g1"), sq("f3")), attacks()));
+    assert!(start.is_legal(Move::double_pawn_push(sq("e2"), sq("e4")), attacks()));
+    // Blocked, empty square, wrong color, wrong flag
+    assert!(!start.is_legal(Move::quiet(sq("f1"), sq("c4")), attacks()));
+    assert!(!start.is_legal(Move::quiet(sq("e4"), sq("e5")), attacks()));
+    assert!(!start.is_legal(Move::quiet(sq("g8"), sq("f6")), attacks()));
+    assert!(!start.is_legal(Move::quiet(sq("e2"), sq("e4")), attacks()));
+
+    // The king may not step onto e2, which the rook on e8 covers
+    let b = board("4r1k1/8/8/8/8/8/8/3K4 w - - 0 1");
+    assert!(b.is_legal(Move::quiet(sq("d1"), sq("d2")), attacks()));
+    assert!(!b.is_legal(Move::quiet(sq("d1"), sq("e2")), attacks()));
+
+    // Castling through the attacked f1
+    let b = board("5rk1/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+    assert!(!b.is_legal(Move::king_castle(sq("e1"), sq("g1")), attacks()));
+    assert!(b.is_legal(Move::queen_castle(sq("e1"), sq("c1")), attacks()));
+}
+
+#[test]
+fn is_legal_agrees_with_generate_legal() {
+    for fen in [
+        START_FEN,
+        KIWIPETE,
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ] {
+        let b = board(fen);
+        let legal = b.generate_legal(attacks());
+        for mv in b.generate_pseudo_legal(attacks()) {
+            assert_eq!(b.is_legal(mv, attacks()), legal.contains(&mv), "{fen} {mv}");
+        }
+    }
+}
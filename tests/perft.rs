@@ -0,0 +1,63 @@
+mod common;
+
+use common::{KIWIPETE, START_FEN, board};
+
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let mut b = board(fen);
+    for (depth, &nodes) in (1..).zip(expected) {
+        assert_eq!(
+            b.perft(depth, common::attacks()),
+            nodes,
+            "{fen} depth {depth}"
+        );
+    }
+}
+
+#[test]
+fn legal_perft_shallow() {
+    assert_perft(START_FEN, &[20, 400, 8902]);
+    assert_perft(KIWIPETE, &[48, 2039, 97862]);
+    assert_perft(POSITION_3, &[14, 191, 2812]);
+    assert_perft(POSITION_4, &[6, 264, 9467]);
+    assert_perft(POSITION_5, &[44, 1486, 62379]);
+}
+
+#[test]
+fn start_position_to_depth_5() {
+    assert_perft(START_FEN, &[20, 400, 8902, 197281, 4865609]);
+}
+
+#[test]
+fn kiwipete_to_depth_4() {
+    assert_perft(KIWIPETE, &[48, 2039, 97862, 4085603]);
+}
+
+#[test]
+fn position_3_to_depth_5() {
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238, 674624]);
+}
+
+#[test]
+fn divide_sums_to_perft() {
+    let mut b = board(KIWIPETE);
+    let divide = b.perft_divide(2, common::attacks());
+    assert_eq!(divide.len(), 48);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 2039);
+
+    let mut start = board(START_FEN);
+    let divide = start.perft_divide(3, common::attacks());
+    let nodes = |uci: &str| {
+        divide
+            .iter()
+            .find(|(mv, _)| mv.to_string() == uci)
+            .unwrap()
+            .1
+    };
+    assert_eq!(nodes("e2e4"), 600);
+    assert_eq!(nodes("g1f3"), 440);
+    assert_eq!(nodes("a2a3"), 380);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::attacks;
+use lemonate::pgn::{PgnError, parse_movetext};
+use lemonate::{Board, SanError};
+
+// Morphy vs Duke Karl / Count Isouard, Paris 1858
+const OPERA_GAME: &str = "1. e4 e5 2. Nf3 d6 3. d4 Bg4 {This is a weak move already.} 4. dxe5
+Bxf3 5. Qxf3 dxe5 6. Bc4 Nf6 7. Qb3 Qe7 8. Nc3 c6 9. Bg5 (9. Qxb7 Qb4+) 9... b5
+10. Nxb5 cxb5 11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7 14. Rd1 Qe6 15. Bxd7+
+Nxd7 16. Qb8+ $1 Nxb8 17. Rd8# 1-0";
+
+#[test]
+fn opera_game_reaches_the_final_position() {
+    let start = Board::starting_position();
+    let moves = parse_movetext(&start, OPERA_GAME, attacks()).unwrap();
+    assert_eq!(moves.len(), 33);
+
+    let mut board = start;
+    for mv in moves {
+        board.make_move(mv);
+    }
+    assert_eq!(
+        board.to_fen(),
+        "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17"
+    );
+}
+
+#[test]
+fn compact_numbers_and_line_comments() {
+    let start = Board::starting_position();
+    let moves = parse_movetext(&start, "1.e4 e5 ; open game\n2.Nf3 *", attacks()).unwrap();
+    let uci: Vec<String> = moves.iter().map(|mv| mv.to_string()).collect();
+    assert_eq!(uci, ["e2e4", "e7e5", "g1f3"]);
+}
+
+#[test]
+fn bad_movetext_is_reported() {
+    let start = Board::starting_position();
+    assert_eq!(
+        parse_movetext(&start, "1. e4 e5 2. Ke3", attacks()),
+        Err(PgnError::InvalidMove {
+            index: 2,
+            san: "Ke3".to_string(),
+            error: SanError::IllegalMove,
+        })
+    );
+    assert_eq!(
+        parse_movetext(&start, "1. e4 {unfinished", attacks()),
+        Err(PgnError::UnterminatedComment)
+    );
+    assert_eq!(
+        parse_movetext(&start, "1. e4 (1. d4", attacks()),
+        Err(PgnError::UnbalancedVariation)
+    );
+}
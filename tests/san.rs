@@ -0,0 +1,122 @@
+mod common;
+
+use common::{KIWIPETE, attacks, board, uci_move};
+use lemonate::SanError;
+
+fn san(fen: &str, uci: &str) -> String {
+    let b = board(fen);
+    b.move_to_san(uci_move(&b, uci), attacks())
+}
+
+#[test]
+fn plain_piece_and_pawn_moves() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_eq!(san(start, "g1f3"), "Nf3");
+    assert_eq!(san(start, "e2e4"), "e4");
+}
+
+#[test]
+fn captures() {
+    let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 0 2";
+    assert_eq!(san(fen, "e4d5"), "exd5");
+
+    assert_eq!(san(KIWIPETE, "e5f7"), "Nxf7");
+    assert_eq!(san(KIWIPETE, "d5e6"), "dxe6");
+}
+
+#[test]
+fn castling() {
+    assert_eq!(san(KIWIPETE, "e1g1"), "O-O");
+    assert_eq!(san(KIWIPETE, "e1c1"), "O-O-O");
+}
+
+#[test]
+fn disambiguation_by_file_rank_or_both() {
+    let by_file = "8/2k5/8/8/R6R/8/8/6K1 w - - 0 1";
+    assert_eq!(san(by_file, "a4e4"), "Rae4");
+    assert_eq!(san(by_file, "h4e4"), "Rhe4");
+
+    let by_rank = "7k/8/8/R7/8/8/8/R5K1 w - - 0 1";
+    assert_eq!(san(by_rank, "a1a3"), "R1a3");
+    assert_eq!(san(by_rank, "a5a3"), "R5a3");
+
+    let by_both = "7K/8/8/7k/8/Q7/8/Q1Q5 w - - 0 1";
+    assert_eq!(san(by_both, "a1b2"), "Qa1b2");
+    assert_eq!(san(by_both, "c1b2"), "Qcb2");
+    assert_eq!(san(by_both, "a3b2"), "Q3b2");
+
+    // A pinned rival can't go there, so it doesn't count
+    assert_eq!(san("k7/8/8/8/8/r7/R7/K6R w - - 0 1", "h1h2"), "Rh2");
+}
+
+#[test]
+fn promotions() {
+    let fen = "k4r2/4P3/8/8/8/8/8/4K3 w - - 0 1";
+    assert_eq!(san(fen, "e7e8q"), "e8=Q+");
+    assert_eq!(san(fen, "e7e8n"), "e8=N");
+    assert_eq!(san(fen, "e7f8r"), "exf8=R+");
+}
+
+#[test]
+fn check_and_mate_suffixes() {
+    let fen = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1";
+    assert_eq!(san(fen, "a1a8"), "Ra8#");
+    assert_eq!(san(fen, "a1a7"), "Ra7");
+
+    assert_eq!(san("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a8"), "Ra8+");
+}
+
+#[test]
+fn every_legal_move_round_trips_through_san() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        KIWIPETE,
+        "7K/8/8/7k/8/Q7/8/Q1Q5 w - - 0 1",
+        "r3k2r/1P4P1/8/8/8/8/1p4p1/R3K2R b KQkq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    ] {
+        let b = board(fen);
+        for mv in b.generate_legal(attacks()) {
+            let san = b.move_to_san(mv, attacks());
+            assert_eq!(b.san_to_move(&san, attacks()), Ok(mv), "{fen} {san}");
+        }
+    }
+}
+
+#[test]
+fn lenient_san_forms_still_parse() {
+    let b = board(KIWIPETE);
+    let parse = |san: &str| b.san_to_move(san, attacks()).map(|mv| mv.to_string());
+
+    assert_eq!(parse("0-0"), Ok("e1g1".to_string()));
+    assert_eq!(parse("O-O-O!?"), Ok("e1c1".to_string()));
+    assert_eq!(parse("Nf7"), Ok("e5f7".to_string()));
+    assert_eq!(parse("Ne5xf7+"), Ok("e5f7".to_string()));
+
+    let promo = board("k4r2/4P3/8/8/8/8/8/4K3 w - - 0 1");
+    assert_eq!(
+        promo.san_to_move("e8Q", attacks()).map(|mv| mv.to_string()),
+        Ok("e7e8q".to_string())
+    );
+}
+
+#[test]
+fn bad_san_gives_a_reason() {
+    let b = board("8/2k5/8/8/R6R/8/8/6K1 w - - 0 1");
+    assert_eq!(
+        b.san_to_move("Re4", attacks()),
+        Err(SanError::AmbiguousMove)
+    );
+    assert_eq!(b.san_to_move("Rb5", attacks()), Err(SanError::IllegalMove));
+    assert_eq!(b.san_to_move("Nf3", attacks()), Err(SanError::IllegalMove));
+    assert_eq!(b.san_to_move("O-O", attacks()), Err(SanError::IllegalMove));
+    assert_eq!(
+        b.san_to_move("Zz9", attacks()),
+        Err(SanError::InvalidFormat)
+    );
+    assert_eq!(b.san_to_move("R", attacks()), Err(SanError::InvalidFormat));
+    assert_eq!(
+        b.san_to_move("e8=K", attacks()),
+        Err(SanError::InvalidFormat)
+    );
+}
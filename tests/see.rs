@@ -0,0 +1,50 @@
+mod common;
+
+use common::{attacks, board, sq, uci_move};
+use lemonate::Move;
+
+#[test]
+fn capturing_a_hanging_piece_wins_it() {
+    let b = board("4k3/8/8/3n4/8/8/8/3RK3 w - - 0 1");
+    assert_eq!(b.see(uci_move(&b, "d1d5"), attacks()), 320);
+}
+
+#[test]
+fn queen_takes_a_defended_pawn_loses_the_queen() {
+    let b = board("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1");
+    assert_eq!(b.see(uci_move(&b, "d1d5"), attacks()), 100 - 900);
+}
+
+#[test]
+fn defended_pawn_is_still_worth_taking_with_a_pawn() {
+    let b = board("4k3/8/2p5/3p4/4P3/8/8/4K3 w - - 0 1");
+    assert_eq!(b.see(uci_move(&b, "e4d5"), attacks()), 0);
+}
+
+#[test]
+fn xray_rook_behind_the_capturer_recaptures() {
+    // Rd2xd5 Rxd5 Rxd5, the d1 rook only sees d5 once d2 has moved
+    let b = board("3r2k1/8/8/3p4/8/8/3R4/3R2K1 w - - 0 1");
+    assert_eq!(b.see(uci_move(&b, "d2d5"), attacks()), 100);
+
+    // Without the back rook the defender wins the exchange
+    let b = board("3r2k1/8/8/3p4/8/8/3R4/6K1 w - - 0 1");
+    assert_eq!(b.see(uci_move(&b, "d2d5"), attacks()), 100 - 500);
+}
+
+#[test]
+fn king_cannot_recapture_onto_a_defended_square() {
+    // Kxe2 would walk into the e8 rook, so the pawn is simply won
+    let b = board("4r1k1/8/8/1b6/8/8/4P3/5K2 b - - 0 1");
+    assert_eq!(b.see(uci_move(&b, "b5e2"), attacks()), 100);
+
+    let b = board("6k1/8/8/1b6/8/8/4P3/5K2 b - - 0 1");
+    assert_eq!(b.see(uci_move(&b, "b5e2"), attacks()), 100 - 330);
+}
+
+#[test]
+fn exchanges_with_dozens_of_attackers_resolve_in_full() {
+    // 35 pieces bear on d4, far more than any game can put on one square
+    let b = board("k2q3Q/q2Q2q1/1QNqNQ2/1nqQqn2/QqQpQqQq/1nqQqn2/1QNqNQ2/q2Q2qK w - - 0 1");
+    assert_eq!(b.see(Move::capture(sq("c2"), sq("d4")), attacks()), 100 - 320);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{KIWIPETE, board, sq};
+use lemonate::{Bitboard, Board, CastlingRights, Color, Piece, PieceType, Square};
+
+#[test]
+fn board_round_trips_as_fen() {
+    let b = board(KIWIPETE);
+    let json = serde_json::to_string(&b).unwrap();
+    assert_eq!(json, format!("\"{KIWIPETE}\""));
+
+    let back: Board = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, b);
+    assert_eq!(back.to_fen(), KIWIPETE);
+
+    assert!(serde_json::from_str::<Board>("\"not a fen\"").is_err());
+}
+
+#[test]
+fn types_round_trip() {
+    assert_eq!(serde_json::to_string(&sq("e4")).unwrap(), "\"e4\"");
+    assert_eq!(serde_json::from_str::<Square>("\"h8\"").unwrap(), sq("h8"));
+    assert!(serde_json::from_str::<Square>("\"i9\"").is_err());
+
+    let piece = Piece {
+        piece_type: PieceType::Queen,
+        color: Color::Black,
+    };
+    let json = serde_json::to_string(&piece).unwrap();
+    assert_eq!(serde_json::from_str::<Piece>(&json).unwrap(), piece);
+
+    let rights = CastlingRights::all();
+    let json = serde_json::to_string(&rights).unwrap();
+    assert_eq!(
+        serde_json::from_str::<CastlingRights>(&json).unwrap(),
+        rights
+    );
+
+    let bb = Bitboard::RANKS[1];
+    let json = serde_json::to_string(&bb).unwrap();
+    assert_eq!(serde_json::from_str::<Bitboard>(&json).unwrap(), bb);
+}
@@ -0,0 +1,145 @@
+use lemonate::{Color, Direction, Square, SquareColor, SquareError};
+
+#[test]
+fn algebraic_round_trips_every_square() {
+    for index in 0..64 {
+        let square = Square::from_index(index);
+        let name = square.to_string();
+        assert_eq!(name.parse::<Square>(), Ok(square), "{name}");
+        assert_eq!(Square::from_algebraic(&name), Ok(square));
+    }
+
+    assert_eq!(Square::from_coords(0, 0).to_string(), "a1");
+    assert_eq!(Square::from_coords(4, 3).to_string(), "e4");
+    assert_eq!(Square::from_coords(7, 7).to_string(), "h8");
+}
+
+#[test]
+fn algebraic_rejects_malformed_squares() {
+    assert_eq!("".parse::<Square>(), Err(SquareError::InvalidLength));
+    assert_eq!("e".parse::<Square>(), Err(SquareError::InvalidLength));
+    assert_eq!("e44".parse::<Square>(), Err(SquareError::InvalidLength));
+    assert_eq!("i4".parse::<Square>(), Err(SquareError::InvalidFile));
+    assert_eq!("E4".parse::<Square>(), Err(SquareError::InvalidFile));
+    assert_eq!("e0".parse::<Square>(), Err(SquareError::InvalidRank));
+    assert_eq!("e9".parse::<Square>(), Err(SquareError::InvalidRank));
+    assert_eq!("é".parse::<Square>(), Err(SquareError::InvalidFile));
+}
+
+#[test]
+fn checked_coords_reject_the_first_value_off_the_board() {
+    assert_eq!(
+        Square::try_from_coords(7, 7),
+        Some(Square::from_coords(7, 7))
+    );
+    assert_eq!(
+        Square::try_from_coords(0, 0),
+        Some(Square::from_coords(0, 0))
+    );
+    assert_eq!(Square::try_from_coords(8, 0), None);
+    assert_eq!(Square::try_from_coords(0, 8), None);
+    assert_eq!(Square::try_from_coords(8, 8), None);
+    assert_eq!(Square::try_from_coords(u8::MAX, 3), None);
+}
+
+#[test]
+fn checked_index_rejects_64_and_above() {
+    assert_eq!(Square::try_from_index(63).map(Square::index), Some(63));
+    assert_eq!(Square::try_from_index(0).map(Square::index), Some(0));
+    assert_eq!(Square::try_from_index(64), None);
+    assert_eq!(Square::try_from_index(usize::MAX), None);
+}
+
+#[test]
+fn from_index_round_trips() {
+    for i in 0..64 {
+        assert_eq!(Square::from_index(i).index(), i);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "square index out of range")]
+fn from_index_asserts_in_debug_builds() {
+    Square::from_index(64);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "ep_target of a square no double push lands on")]
+fn ep_target_asserts_a_double_push_landing_square() {
+    // a1 - 8 would underflow rather than name a square
+    Square::ep_target(Square::A1, Color::White);
+}
+
+#[test]
+fn direction_deltas() {
+    assert_eq!(Direction::NORTH.deltas(), (0, 1));
+    assert_eq!(Direction::NORTHEAST.deltas(), (1, 1));
+    assert_eq!(Direction::EAST.deltas(), (1, 0));
+    assert_eq!(Direction::SOUTHEAST.deltas(), (1, -1));
+    assert_eq!(Direction::SOUTH.deltas(), (0, -1));
+    assert_eq!(Direction::SOUTHWEST.deltas(), (-1, -1));
+    assert_eq!(Direction::WEST.deltas(), (-1, 0));
+    assert_eq!(Direction::NORTHWEST.deltas(), (-1, 1));
+}
+
+#[test]
+fn offset_stops_at_every_edge() {
+    for index in 0..64 {
+        let square = Square::from_index(index);
+        for dir in Direction::ALL {
+            let (df, dr) = dir.deltas();
+            let file = square.file() as i8 + df;
+            let rank = square.rank() as i8 + dr;
+            let expected = ((0..8).contains(&file) && (0..8).contains(&rank))
+                .then(|| Square::from_coords(file as u8, rank as u8));
+            assert_eq!(square.offset(dir), expected, "{square} {dir:?}");
+        }
+    }
+
+    // The raw index step would wrap onto the next rank here
+    assert_eq!(Square::from_coords(7, 3).offset(Direction::EAST), None);
+    assert_eq!(Square::from_coords(0, 3).offset(Direction::WEST), None);
+    assert_eq!(Square::from_coords(7, 0).offset(Direction::NORTHEAST), None);
+    assert_eq!(Square::from_coords(0, 7).offset(Direction::SOUTHWEST), None);
+    assert_eq!(Square::from_coords(3, 7).offset(Direction::NORTH), None);
+    assert_eq!(Square::from_coords(3, 0).offset(Direction::SOUTH), None);
+}
+
+#[test]
+fn chebyshev_and_manhattan_distances() {
+    let sq = |name: &str| name.parse::<Square>().unwrap();
+    let cases = [
+        ("e4", "e4", 0, 0),
+        ("e4", "f5", 1, 2),
+        ("e4", "e5", 1, 1),
+        ("a1", "h8", 7, 14),
+        ("h1", "a8", 7, 14),
+        ("b2", "d7", 5, 7),
+    ];
+
+    for (a, b, chebyshev, manhattan) in cases {
+        assert_eq!(sq(a).distance(sq(b)), chebyshev, "{a} {b}");
+        assert_eq!(sq(b).distance(sq(a)), chebyshev, "{b} {a}");
+        assert_eq!(sq(a).manhattan_distance(sq(b)), manhattan, "{a} {b}");
+        assert_eq!(sq(b).manhattan_distance(sq(a)), manhattan, "{b} {a}");
+    }
+}
+
+#[test]
+fn square_colors() {
+    let sq = |name: &str| name.parse::<Square>().unwrap();
+    for (name, color) in [
+        ("a1", SquareColor::Dark),
+        ("h1", SquareColor::Light),
+        ("a8", SquareColor::Light),
+        ("h8", SquareColor::Dark),
+        ("d4", SquareColor::Dark),
+        ("e4", SquareColor::Light),
+        ("d5", SquareColor::Light),
+        ("e5", SquareColor::Dark),
+    ] {
+        assert_eq!(sq(name).square_color(), color, "{name}");
+    }
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::{attacks, board, play};
+use lemonate::{Board, GameStatus};
+
+const SHUFFLE: [&str; 4] = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+#[test]
+fn knight_shuffle_twice_is_threefold() {
+    let mut b = Board::starting_position();
+    play(&mut b, &SHUFFLE);
+    assert!(!b.is_threefold_repetition());
+
+    play(&mut b, &SHUFFLE);
+    assert!(b.is_threefold_repetition());
+    assert_eq!(b.status(attacks()), GameStatus::ThreefoldRepetition);
+}
+
+#[test]
+fn shuffle_interrupted_by_a_pawn_move_is_not_threefold() {
+    let mut b = Board::starting_position();
+    play(&mut b, &SHUFFLE);
+    play(&mut b, &["e2e4", "e7e5"]);
+    play(&mut b, &SHUFFLE);
+    assert!(!b.is_threefold_repetition());
+    assert_eq!(b.status(attacks()), GameStatus::Ongoing);
+}
+
+#[test]
+fn fifty_move_rule_at_100_halfmoves() {
+    assert!(!board("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").is_fifty_move_draw());
+    let b = board("4k3/8/8/8/8/8/8/R3K3 w - - 100 80");
+    assert!(b.is_fifty_move_draw());
+    assert_eq!(b.status(attacks()), GameStatus::FiftyMoveDraw);
+
+    // A mating move on the fiftieth move still wins
+    let mut mate = board("6k1/5ppp/8/8/8/8/8/R5K1 w - - 99 80");
+    play(&mut mate, &["a1a8"]);
+    assert_eq!(mate.status(attacks()), GameStatus::Checkmate);
+}
+
+#[test]
+fn insufficient_material_cases() {
+    for fen in [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/4KN2 w - - 0 1",
+        "4k3/8/8/8/8/8/8/4KB2 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/4K3 w - - 0 1",
+        // Bishops all on dark squares, on both sides
+        "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/B1B1K3 w - - 0 1",
+    ] {
+        let b = board(fen);
+        assert!(b.is_insufficient_material(), "{fen}");
+        assert_eq!(
+            b.status(attacks()),
+            GameStatus::InsufficientMaterial,
+            "{fen}"
+        );
+    }
+}
+
+#[test]
+fn enough_material_to_mate() {
+    for fen in [
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/R3K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/3QK3 w - - 0 1",
+        // Opposite colored bishops, and bishop against knight
+        "4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/4KB2 w - - 0 1",
+        // K+N+N can't force mate but can mate, so it is not a draw by rule
+        "4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1",
+    ] {
+        assert!(!board(fen).is_insufficient_material(), "{fen}");
+    }
+}
+
+#[test]
+fn back_rank_mate_is_checkmate() {
+    let b = board("R5k1/5ppp/8/8/8/8/8/6K1 b - - 1 1");
+    assert_eq!(b.status(attacks()), GameStatus::Checkmate);
+}
+
+#[test]
+fn classic_stalemate() {
+    let b = board("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+    assert!(!b.in_check(attacks()));
+    assert_eq!(b.status(attacks()), GameStatus::Stalemate);
+}
+
+#[test]
+fn start_position_is_ongoing() {
+    assert_eq!(
+        Board::starting_position().status(attacks()),
+        GameStatus::Ongoing
+    );
+}
@@ -0,0 +1,220 @@
+use lemonate::{
+    CastlingRights, CastlingSide, Color, Direction, FenError, Move, MoveFlag, Piece, PieceType,
+    Square,
+};
+
+#[test]
+fn piece_fen_chars_round_trip() {
+    for ch in "PNBRQKpnbrqk".chars() {
+        let piece = Piece::from_fen_char(ch).unwrap();
+        assert_eq!(piece.to_fen_char(), ch);
+        assert_eq!(ch.is_ascii_uppercase(), matches!(piece.color, Color::White));
+    }
+}
+
+#[test]
+fn piece_from_fen_char_rejects_other_letters() {
+    for ch in ['x', 'X', '1', ' ', '/'] {
+        assert!(matches!(
+            Piece::from_fen_char(ch),
+            Err(FenError::InvalidPiece)
+        ));
+    }
+}
+
+#[test]
+fn move_flags_and_uci_strings() {
+    let sq = |name: &str| name.parse::<Square>().unwrap();
+    let cases = [
+        (Move::quiet(sq("g1"), sq("f3")), MoveFlag::Quiet, "g1f3"),
+        (Move::capture(sq("e4"), sq("d5")), MoveFlag::Capture, "e4d5"),
+        (
+            Move::double_pawn_push(sq("e2"), sq("e4")),
+            MoveFlag::DoublePawnPush,
+            "e2e4",
+        ),
+        (
+            Move::en_passant(sq("e5"), sq("d6")),
+            MoveFlag::EnPassant,
+            "e5d6",
+        ),
+        (
+            Move::king_castle(sq("e1"), sq("g1")),
+            MoveFlag::KingCastle,
+            "e1g1",
+        ),
+        (
+            Move::queen_castle(sq("e8"), sq("c8")),
+            MoveFlag::QueenCastle,
+            "e8c8",
+        ),
+        (
+            Move::promotion(sq("e7"), sq("e8"), PieceType::Queen),
+            MoveFlag::Promotion,
+            "e7e8q",
+        ),
+        (
+            Move::promotion_capture(sq("b2"), sq("a1"), PieceType::Knight),
+            MoveFlag::PromotionCapture,
+            "b2a1n",
+        ),
+    ];
+
+    for (mv, flag, uci) in cases {
+        assert_eq!(mv.flag(), flag);
+        assert_eq!(mv.to_string(), uci);
+        assert_eq!(mv.from().to_string(), &uci[..2]);
+        assert_eq!(mv.to().to_string(), &uci[2..4]);
+
+        assert_eq!(
+            mv.is_capture(),
+            matches!(
+                flag,
+                MoveFlag::Capture | MoveFlag::EnPassant | MoveFlag::PromotionCapture
+            )
+        );
+        assert_eq!(
+            mv.is_promotion(),
+            matches!(flag, MoveFlag::Promotion | MoveFlag::PromotionCapture)
+        );
+        assert_eq!(mv.promotion_piece().is_some(), mv.is_promotion());
+        assert_eq!(
+            mv.is_castle(),
+            matches!(flag, MoveFlag::KingCastle | MoveFlag::QueenCastle)
+        );
+    }
+}
+
+#[test]
+fn castling_rights_removal_per_color_and_side() {
+    let mut rights = CastlingRights::all();
+    rights.remove_color(Color::White);
+    assert!(!rights.can_castle(Color::White, CastlingSide::Kingside));
+    assert!(!rights.can_castle(Color::White, CastlingSide::Queenside));
+    assert!(rights.can_castle(Color::Black, CastlingSide::Kingside));
+    assert!(rights.can_castle(Color::Black, CastlingSide::Queenside));
+
+    rights.remove_queenside(Color::Black);
+    assert!(rights.can_castle(Color::Black, CastlingSide::Kingside));
+    rights.remove_kingside(Color::Black);
+    assert_eq!(rights, CastlingRights::none());
+}
+
+#[test]
+fn castling_rights_fen_round_trip() {
+    assert_eq!(
+        CastlingRights::from_fen("-").unwrap(),
+        CastlingRights::none()
+    );
+    assert_eq!(CastlingRights::none().to_fen(), "-");
+
+    assert_eq!(
+        CastlingRights::from_fen("KQkq").unwrap(),
+        CastlingRights::all()
+    );
+    assert_eq!(CastlingRights::all().to_fen(), "KQkq");
+
+    let rights = CastlingRights::from_fen("Kq").unwrap();
+    assert!(rights.can_castle(Color::White, CastlingSide::Kingside));
+    assert!(!rights.can_castle(Color::White, CastlingSide::Queenside));
+    assert!(!rights.can_castle(Color::Black, CastlingSide::Kingside));
+    assert!(rights.can_castle(Color::Black, CastlingSide::Queenside));
+    assert_eq!(rights.to_fen(), "Kq");
+
+    // Out of order input still comes back canonical
+    assert_eq!(CastlingRights::from_fen("qK").unwrap().to_fen(), "Kq");
+}
+
+#[test]
+fn castling_rights_from_fen_rejects_bad_fields() {
+    for field in ["", "KK", "X", "K-", "KQkqq"] {
+        assert!(
+            matches!(
+                CastlingRights::from_fen(field),
+                Err(FenError::InvalidCastlingRights)
+            ),
+            "{field:?}"
+        );
+    }
+}
+
+#[test]
+fn color_opposite_and_all() {
+    assert_eq!(Color::White.opposite(), Color::Black);
+    assert_eq!(Color::Black.opposite(), Color::White);
+
+    assert_eq!(Color::ALL, [Color::White, Color::Black]);
+    for (i, color) in Color::ALL.into_iter().enumerate() {
+        assert_eq!(color.index(), i);
+    }
+}
+
+#[test]
+fn piece_type_all_and_char_round_trip() {
+    assert_eq!(
+        PieceType::ALL,
+        [
+            PieceType::Pawn,
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Rook,
+            PieceType::Queen,
+            PieceType::King,
+        ]
+    );
+    for (i, piece_type) in PieceType::ALL.into_iter().enumerate() {
+        assert_eq!(piece_type.index(), i);
+
+        let ch = piece_type.to_char();
+        assert!(ch.is_ascii_lowercase());
+        assert_eq!(PieceType::from_char(ch), Some(piece_type));
+        assert_eq!(
+            PieceType::from_char(ch.to_ascii_uppercase()),
+            Some(piece_type)
+        );
+    }
+    assert_eq!(PieceType::from_char('x'), None);
+}
+
+#[test]
+fn each_type_has_one_public_definition() {
+    // The glob re-exports in lib.rs and the module paths must name the same
+    // types, a second definition anywhere would make these fail to compile
+    let _: lemonate::Board = lemonate::board::Board::starting_position();
+    let rights: lemonate::types::castling::CastlingRights = lemonate::CastlingRights::all();
+    let _: lemonate::CastlingRights = rights;
+    let magic: lemonate::magic::Magic = lemonate::Magic::default();
+    assert_eq!(magic.hash(lemonate::Bitboard::FULL), 0);
+}
+
+#[test]
+fn direction_arithmetic() {
+    assert_eq!(Direction::NORTH.relative(Color::Black), Direction::SOUTH);
+    assert_eq!(
+        Direction::NORTHEAST.relative(Color::Black),
+        Direction::SOUTHWEST
+    );
+    assert_eq!(Direction::NORTH.relative(Color::White), Direction::NORTH);
+
+    assert_eq!(Direction::from_deltas(1, 1), Some(Direction::NORTHEAST));
+    assert_eq!(Direction::from_deltas(-1, 0), Some(Direction::WEST));
+    assert_eq!(Direction::from_deltas(0, 0), None);
+    assert_eq!(Direction::from_deltas(2, 1), None);
+
+    for dir in Direction::ALL {
+        assert_eq!(dir.flip().flip(), dir);
+        let (file, rank) = dir.deltas();
+        assert_eq!(dir.flip().deltas(), (-file, -rank));
+        assert_eq!(Direction::from_deltas(file, rank), Some(dir));
+    }
+}
+
+#[test]
+fn named_square_constants() {
+    assert_eq!(Square::E4, Square::from_coords(4, 3));
+    assert_eq!(Square::A1.index(), 0);
+    assert_eq!(Square::H1.index(), 7);
+    assert_eq!(Square::A8.index(), 56);
+    assert_eq!(Square::H8.index(), 63);
+    assert_eq!(Square::D5, "d5".parse().unwrap());
+}
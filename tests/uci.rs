@@ -0,0 +1,150 @@
+mod common;
+
+use common::{KIWIPETE, attacks, board, sq};
+use lemonate::{Board, Move, MoveParseError, PieceType, SquareError, UciError};
+
+#[test]
+fn parses_castling_from_the_king_move() {
+    let b = board(KIWIPETE);
+    assert_eq!(
+        b.parse_uci_move("e1g1"),
+        Ok(Move::king_castle(sq("e1"), sq("g1")))
+    );
+    assert_eq!(
+        b.parse_uci_move("e1c1"),
+        Ok(Move::queen_castle(sq("e1"), sq("c1")))
+    );
+    assert_eq!(
+        b.parse_uci_move("e1d1"),
+        Ok(Move::quiet(sq("e1"), sq("d1")))
+    );
+}
+
+#[test]
+fn parses_en_passant_and_double_pushes() {
+    let b = board("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3");
+    assert_eq!(
+        b.parse_uci_move("e5f6"),
+        Ok(Move::en_passant(sq("e5"), sq("f6")))
+    );
+    assert_eq!(
+        b.parse_uci_move("d2d4"),
+        Ok(Move::double_pawn_push(sq("d2"), sq("d4")))
+    );
+    assert_eq!(
+        b.parse_uci_move("e5e6"),
+        Ok(Move::quiet(sq("e5"), sq("e6")))
+    );
+}
+
+#[test]
+fn parses_promotions() {
+    let b = board("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1");
+    assert_eq!(
+        b.parse_uci_move("e7e8q"),
+        Ok(Move::promotion(sq("e7"), sq("e8"), PieceType::Queen))
+    );
+    assert_eq!(
+        b.parse_uci_move("e7d8n"),
+        Ok(Move::promotion_capture(
+            sq("e7"),
+            sq("d8"),
+            PieceType::Knight
+        ))
+    );
+    assert_eq!(
+        b.parse_uci_move("e7e8"),
+        Err(MoveParseError::InvalidPromotion)
+    );
+    assert_eq!(
+        b.parse_uci_move("e7e8k"),
+        Err(MoveParseError::InvalidPromotion)
+    );
+    assert_eq!(
+        b.parse_uci_move("e1e2q"),
+        Err(MoveParseError::InvalidPromotion)
+    );
+}
+
+#[test]
+fn rejects_malformed_and_impossible_moves() {
+    let b = Board::starting_position();
+    assert_eq!(b.parse_uci_move("e2"), Err(MoveParseError::InvalidLength));
+    assert_eq!(
+        b.parse_uci_move("e2i4"),
+        Err(MoveParseError::InvalidSquare(SquareError::InvalidFile))
+    );
+    assert_eq!(b.parse_uci_move("e4e5"), Err(MoveParseError::NoPieceToMove));
+    assert_eq!(b.parse_uci_move("e7e5"), Err(MoveParseError::NoPieceToMove));
+    assert_eq!(
+        b.parse_uci_move("d1d2"),
+        Err(MoveParseError::OwnPieceOnTarget)
+    );
+}
+
+#[test]
+fn every_legal_move_round_trips_through_uci() {
+    for fen in [
+        KIWIPETE,
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "r3k2r/1P4P1/8/8/8/8/1p4p1/R3K2R b KQkq - 0 1",
+    ] {
+        let b = board(fen);
+        for mv in b.generate_legal(attacks()) {
+            assert_eq!(b.parse_uci_move(&mv.to_uci()), Ok(mv), "{fen} {mv}");
+        }
+    }
+}
+
+#[test]
+fn position_startpos_with_moves() {
+    let mut b = Board::new();
+    b.apply_uci_position(
+        &["position", "startpos", "moves", "e2e4", "c7c5", "g1f3"],
+        attacks(),
+    )
+    .unwrap();
+    assert_eq!(
+        b.to_fen(),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+    );
+
+    b.apply_uci_position(&["startpos"], attacks()).unwrap();
+    assert_eq!(b.to_fen(), Board::starting_position().to_fen());
+}
+
+#[test]
+fn position_fen_with_moves() {
+    let mut b = Board::new();
+    let mut tokens = vec!["fen"];
+    tokens.extend(KIWIPETE.split_whitespace());
+    tokens.extend(["moves", "e1g1", "h3g2"]);
+
+    b.apply_uci_position(&tokens, attacks()).unwrap();
+    assert_eq!(
+        b.to_fen(),
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q2/PPPBBPpP/R4RK1 w kq - 0 2"
+    );
+}
+
+#[test]
+fn position_errors_leave_the_board_alone() {
+    let mut b = board(KIWIPETE);
+    assert!(matches!(
+        b.apply_uci_position(&["startpos", "moves", "e2e5"], attacks()),
+        Err(UciError::IllegalMove(_))
+    ));
+    assert!(matches!(
+        b.apply_uci_position(&["startpos", "moves", "e2"], attacks()),
+        Err(UciError::InvalidMove(_, MoveParseError::InvalidLength))
+    ));
+    assert!(matches!(
+        b.apply_uci_position(&["fen", "8/8", "w", "-", "-"], attacks()),
+        Err(UciError::InvalidFen(_))
+    ));
+    assert!(matches!(
+        b.apply_uci_position(&["moves", "e2e4"], attacks()),
+        Err(UciError::MissingPosition)
+    ));
+    assert_eq!(b.to_fen(), KIWIPETE);
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use common::{KIWIPETE, attacks, board, play, sq};
+use lemonate::{Board, BoardBuilder, CastlingSide, Color, Piece, PieceType, PositionError};
+
+fn validate(fen: &str) -> Result<(), PositionError> {
+    board(fen).validate(attacks())
+}
+
+#[test]
+fn reachable_positions_validate() {
+    assert_eq!(Board::starting_position().validate(attacks()), Ok(()));
+    assert_eq!(validate(KIWIPETE), Ok(()));
+
+    let mut b = Board::starting_position();
+    play(&mut b, &["e2e4", "c7c5", "e4e5", "d7d5"]);
+    assert_eq!(b.validate(attacks()), Ok(()));
+}
+
+#[test]
+fn wrong_king_counts_are_rejected() {
+    assert_eq!(
+        validate("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"),
+        Err(PositionError::KingCount {
+            color: Color::White,
+            count: 2
+        })
+    );
+    assert_eq!(
+        validate("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+        Err(PositionError::KingCount {
+            color: Color::Black,
+            count: 0
+        })
+    );
+}
+
+#[test]
+fn pawns_on_back_ranks_are_rejected() {
+    assert_eq!(
+        validate("3Pk3/8/8/8/8/8/8/4K3 b - - 0 1"),
+        Err(PositionError::PawnOnBackRank(sq("d8")))
+    );
+    assert_eq!(
+        validate("4k3/8/8/8/8/8/8/p3K3 w - - 0 1"),
+        Err(PositionError::PawnOnBackRank(sq("a1")))
+    );
+}
+
+#[test]
+fn side_not_to_move_in_check_is_rejected() {
+    assert_eq!(validate("4k3/8/8/8/8/8/8/4KR2 b - - 0 1"), Ok(()));
+    assert_eq!(
+        validate("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1"),
+        Err(PositionError::OpponentInCheck)
+    );
+}
+
+#[test]
+fn en_passant_needs_a_double_pushed_pawn() {
+    assert_eq!(validate("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1"), Ok(()));
+    // Wrong rank for white to move. FEN already refuses this, a builder
+    // does not
+    let king = |color| Piece {
+        piece_type: PieceType::King,
+        color,
+    };
+    let pawn = |color| Piece {
+        piece_type: PieceType::Pawn,
+        color,
+    };
+    assert_eq!(
+        BoardBuilder::new()
+            .piece(sq("e1"), king(Color::White))
+            .piece(sq("e8"), king(Color::Black))
+            .piece(sq("d4"), pawn(Color::White))
+            .piece(sq("e4"), pawn(Color::Black))
+            .en_passant(sq("d3"))
+            .build(attacks())
+            .map(|_| ()),
+        Err(PositionError::InvalidEnPassant(sq("d3")))
+    );
+    // No pawn in front of the square
+    assert_eq!(
+        validate("4k3/8/8/4P3/8/8/8/4K3 w - d6 0 1"),
+        Err(PositionError::InvalidEnPassant(sq("d6")))
+    );
+}
+
+#[test]
+fn castling_rights_need_king_and_rook_at_home() {
+    assert_eq!(validate("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"), Ok(()));
+    assert_eq!(
+        validate("r3k2r/8/8/8/8/8/8/R3K3 w KQkq - 0 1"),
+        Err(PositionError::InvalidCastlingRights(
+            Color::White,
+            CastlingSide::Kingside
+        ))
+    );
+    assert_eq!(validate("r3k2r/8/8/8/8/8/8/R2K3R w kq - 0 1"), Ok(()));
+    assert_eq!(
+        validate("4k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"),
+        Err(PositionError::InvalidCastlingRights(
+            Color::Black,
+            CastlingSide::Queenside
+        ))
+    );
+}
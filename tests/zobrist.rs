@@ -0,0 +1,97 @@
+mod common;
+
+use common::{board, play, position};
+use lemonate::{Board, ZobristKeys};
+
+fn after(moves: &[&str]) -> Board {
+    let mut b = Board::starting_position();
+    play(&mut b, moves);
+    b
+}
+
+#[test]
+fn transpositions_hash_equal() {
+    let a = after(&["g1f3", "g8f6", "b1c3", "b8c6"]);
+    let b = after(&["b1c3", "b8c6", "g1f3", "g8f6"]);
+    assert_eq!(a.compute_hash(), b.compute_hash());
+    assert_eq!(position(&a), position(&b));
+
+    // Same placement, other side to move
+    let c = after(&["g1f3", "g8f6", "b1c3", "b8c6", "f3g1", "f6g8", "g1f3"]);
+    assert_ne!(c.compute_hash(), a.compute_hash());
+}
+
+#[test]
+fn hash_depends_on_castling_and_en_passant() {
+    let with_rights = board("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    let without = board("r3k2r/8/8/8/8/8/8/R3K2R w Kkq - 0 1");
+    assert_ne!(with_rights.compute_hash(), without.compute_hash());
+
+    let ep = board("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1");
+    let no_ep = board("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1");
+    assert_ne!(ep.compute_hash(), no_ep.compute_hash());
+}
+
+#[test]
+fn incremental_hash_matches_recompute() {
+    // Castling both ways, en passant, a promotion and captures
+    let mut b = board("r3k2r/pPpp1ppp/8/3Pp3/8/8/P1PP1PPP/R3K2R w KQkq e6 0 1");
+    for mv in [
+        "d5e6", "e8g8", "e1c1", "d7e6", "b7a8q", "f8a8", "a2a4", "c7c5",
+    ] {
+        play(&mut b, &[mv]);
+        // The incrementally updated hash against the one from_fen recomputes
+        assert_eq!(position(&b), position(&board(&b.to_fen())), "after {mv}");
+    }
+}
+
+#[test]
+fn hash_accessor_matches_across_move_orders() {
+    // 1.e4 e5 2.Nf3 Nc6 against 1.Nf3 e5 2.e4 Nc6
+    let a = after(&["e2e4", "e7e5", "g1f3", "b8c6"]);
+    let b = after(&["g1f3", "e7e5", "e2e4", "b8c6"]);
+    assert_eq!(a.hash(), b.hash());
+    assert_eq!(a.hash(), a.compute_hash());
+
+    // Ending on a double push leaves an en passant square behind, so
+    // 1.Nf3 Nc6 2.e4 e5 only matches orders that also end with e5
+    let c = after(&["g1f3", "b8c6", "e2e4", "e7e5"]);
+    let d = after(&["e2e4", "b8c6", "g1f3", "e7e5"]);
+    assert_ne!(a.hash(), c.hash());
+    assert_eq!(c.hash(), d.hash());
+}
+
+#[test]
+fn zobrist_keys_rebuild_the_hash() {
+    let b = board("r3k2r/8/8/8/4Pp2/8/8/R3K2R b Kq e3 0 1");
+    let keys = ZobristKeys::get();
+
+    let mut hash = 0;
+    for square in b.occupancy() {
+        hash ^= keys.piece(square, b.piece_at(square).unwrap());
+    }
+    hash ^= keys.side;
+    hash ^= keys.castling[0] ^ keys.castling[3];
+    hash ^= keys.en_passant[4];
+
+    assert_eq!(hash, b.hash());
+}
+
+#[test]
+fn repeated_positions_share_the_repetition_key() {
+    let mut b = Board::starting_position();
+    let start = b.repetition_key();
+    assert_eq!(start, b.hash());
+
+    play(&mut b, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(b.repetition_key(), start);
+    // The move counters moved on but are not part of the key
+    assert_eq!(
+        b.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 4 3"
+    );
+
+    play(&mut b, &["g1f3"]);
+    assert_ne!(b.repetition_key(), start);
+    assert_eq!(b.repetition_key(), b.hash());
+}